@@ -0,0 +1,26 @@
+use noise::Noise;
+
+/// Returns the sum of two noises.
+pub struct Add<A, B> {
+    /// The first combined noise.
+    first: A,
+    /// The second combined noise.
+    second: B,
+}
+
+impl<A: Noise, B: Noise> Add<A, B> {
+    /// Creates the sum of `first` and `second`.
+    pub fn new(first:A, second:B) -> Add<A, B> {
+        Add {
+            first: first,
+            second: second,
+        }
+    }
+
+    /// Returns the sum of two values of the sources.
+    fn combine(&self, a:f32, b:f32) -> f32 {
+        a + b
+    }
+}
+
+impl_combiner_noise!(Add)
@@ -0,0 +1,80 @@
+use math::lerp;
+use noise::{Noise, Noise4D};
+
+/// Blends two noises, weighted by a control noise.
+///
+/// Where the control is -1 the output is the first noise, where it is 1
+/// the output is the second one, in between they are linearly
+/// interpolated.
+pub struct Blend<A, B, C> {
+    /// The noise output where the control is -1.
+    first: A,
+    /// The noise output where the control is 1.
+    second: B,
+    /// Weights the two noises.
+    control: C,
+}
+
+impl<A: Noise, B: Noise, C: Noise> Blend<A, B, C> {
+    /// Creates a blend of `first` and `second` weighted by `control`.
+    pub fn new(first:A, second:B, control:C) -> Blend<A, B, C> {
+        Blend {
+            first: first,
+            second: second,
+            control: control,
+        }
+    }
+
+    /// Interpolates between values of the first and second noises,
+    /// weighted by a value of the control.
+    fn blend(&self, control:f32, first:f32, second:f32) -> f32 {
+        lerp((control + 1.0) / 2.0, first, second)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<A: Noise, B: Noise, C: Noise> Noise for Blend<A, B, C> {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.blend(self.control.get_value(x, y, z),
+                   self.first.get_value(x, y, z),
+                   self.second.get_value(x, y, z))
+    }
+
+    /// Returns the noise value at the point(x).
+    fn get_1d(&self, x:f32) -> f32 {
+        self.blend(self.control.get_1d(x), self.first.get_1d(x), self.second.get_1d(x))
+    }
+
+    /// Returns the noise value at the point(x,y).
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.blend(self.control.get_2d(x, y),
+                   self.first.get_2d(x, y),
+                   self.second.get_2d(x, y))
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<A: Noise4D, B: Noise4D, C: Noise4D> Noise4D for Blend<A, B, C> {
+    /// Returns the noise value at the point(x,y,z,w).
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.blend(self.control.get_4d(x, y, z, w),
+                   self.first.get_4d(x, y, z, w),
+                   self.second.get_4d(x, y, z, w))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noises::perlin::Perlin;
+    use super::Blend;
+
+    #[test]
+    fn control_weights_the_noises() {
+        let blend = Blend::new(Perlin::new(), Perlin::new(), Perlin::new());
+        assert_eq!(blend.blend(-1.0, -0.5, 0.7), -0.5);
+        assert!((blend.blend(-0.5, -0.5, 0.7) + 0.2).abs() < 1e-6);
+        assert!((blend.blend(0.0, -0.5, 0.7) - 0.1).abs() < 1e-6);
+        assert!((blend.blend(1.0, -0.5, 0.7) - 0.7).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,26 @@
+use noise::Noise;
+
+/// Returns the larger value of two noises.
+pub struct Max<A, B> {
+    /// The first combined noise.
+    first: A,
+    /// The second combined noise.
+    second: B,
+}
+
+impl<A: Noise, B: Noise> Max<A, B> {
+    /// Creates the maximum of `first` and `second`.
+    pub fn new(first:A, second:B) -> Max<A, B> {
+        Max {
+            first: first,
+            second: second,
+        }
+    }
+
+    /// Returns the larger of two values of the sources.
+    fn combine(&self, a:f32, b:f32) -> f32 {
+        a.max(b)
+    }
+}
+
+impl_combiner_noise!(Max)
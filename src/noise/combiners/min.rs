@@ -0,0 +1,26 @@
+use noise::Noise;
+
+/// Returns the smaller value of two noises.
+pub struct Min<A, B> {
+    /// The first combined noise.
+    first: A,
+    /// The second combined noise.
+    second: B,
+}
+
+impl<A: Noise, B: Noise> Min<A, B> {
+    /// Creates the minimum of `first` and `second`.
+    pub fn new(first:A, second:B) -> Min<A, B> {
+        Min {
+            first: first,
+            second: second,
+        }
+    }
+
+    /// Returns the smaller of two values of the sources.
+    fn combine(&self, a:f32, b:f32) -> f32 {
+        a.min(b)
+    }
+}
+
+impl_combiner_noise!(Min)
@@ -0,0 +1,42 @@
+/// Implements the noise generator traits of a combiner keeping its sources
+/// in its `first` and `second` fields and combining their values with its
+/// `combine` method, e.g. `impl_combiner_noise!(Add)`. Every dimension is
+/// forwarded to the same dimension of the sources, 4D where both sources
+/// support it.
+macro_rules! impl_combiner_noise(
+    ($name:ident) => (
+        /// Implements the noise generator common trait.
+        impl<A: ::noise::Noise, B: ::noise::Noise> ::noise::Noise for $name<A, B> {
+            /// Returns the noise value at the point(x,y,z).
+            fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+                self.combine(self.first.get_value(x, y, z), self.second.get_value(x, y, z))
+            }
+
+            /// Returns the noise value at the point(x).
+            fn get_1d(&self, x:f32) -> f32 {
+                self.combine(self.first.get_1d(x), self.second.get_1d(x))
+            }
+
+            /// Returns the noise value at the point(x,y).
+            fn get_2d(&self, x:f32, y:f32) -> f32 {
+                self.combine(self.first.get_2d(x, y), self.second.get_2d(x, y))
+            }
+        }
+
+        /// Implements the 4D noise generator trait.
+        impl<A: ::noise::Noise4D, B: ::noise::Noise4D> ::noise::Noise4D for $name<A, B> {
+            /// Returns the noise value at the point(x,y,z,w).
+            fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+                self.combine(self.first.get_4d(x, y, z, w), self.second.get_4d(x, y, z, w))
+            }
+        }
+    )
+)
+
+pub mod add;
+pub mod blend;
+pub mod max;
+pub mod min;
+pub mod multiply;
+pub mod power;
+pub mod select;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use noise::Noise;
+
+/// Returns the product of two noises.
+pub struct Multiply<A, B> {
+    /// The first combined noise.
+    first: A,
+    /// The second combined noise.
+    second: B,
+}
+
+impl<A: Noise, B: Noise> Multiply<A, B> {
+    /// Creates the product of `first` and `second`.
+    pub fn new(first:A, second:B) -> Multiply<A, B> {
+        Multiply {
+            first: first,
+            second: second,
+        }
+    }
+
+    /// Returns the product of two values of the sources.
+    fn combine(&self, a:f32, b:f32) -> f32 {
+        a * b
+    }
+}
+
+impl_combiner_noise!(Multiply)
@@ -0,0 +1,54 @@
+use noise::Noise;
+
+/// Raises a noise to the power of another one.
+///
+/// As with `powf`, a negative base raised to a non integer exponent
+/// gives NaN.
+pub struct Power<A, B> {
+    /// The base.
+    first: A,
+    /// The exponent.
+    second: B,
+}
+
+impl<A: Noise, B: Noise> Power<A, B> {
+    /// Creates `first` raised to the power of `second`.
+    pub fn new(first:A, second:B) -> Power<A, B> {
+        Power {
+            first: first,
+            second: second,
+        }
+    }
+
+    /// Raises a value of the first source to a value of the second.
+    fn combine(&self, a:f32, b:f32) -> f32 {
+        a.powf(b)
+    }
+}
+
+impl_combiner_noise!(Power)
+
+#[cfg(test)]
+mod test {
+    use noises::perlin::Perlin;
+    use super::Power;
+
+    fn power(base:f32, exponent:f32) -> f32 {
+        Power::new(Perlin::new(), Perlin::new()).combine(base, exponent)
+    }
+
+    #[test]
+    fn negative_bases_take_integer_exponents() {
+        assert_eq!(power(-2.0, 2.0), 4.0);
+        assert_eq!(power(-2.0, 3.0), -8.0);
+        assert_eq!(power(-2.0, -1.0), -0.5);
+        assert_eq!(power(-0.5, 0.0), 1.0);
+    }
+
+    #[test]
+    fn negative_bases_give_nan_with_other_exponents() {
+        assert!(power(-2.0, 0.5).is_nan());
+        assert!(power(-0.3, 1.7).is_nan());
+        assert_eq!(power(2.0, 0.5), 2.0f32.sqrt());
+    }
+}
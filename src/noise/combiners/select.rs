@@ -0,0 +1,166 @@
+use math::{cubic_curve, lerp};
+use noise::{Noise, Noise4D};
+
+/// Selects one of two noises, depending on a control noise.
+///
+/// Where the control lies within the bounds the output is the second
+/// noise, elsewhere it is the first one. With an edge falloff, the two
+/// noises are smoothly blended across the bounds instead of switching
+/// abruptly.
+pub struct Select<A, B, C> {
+    /// The noise output where the control is out of the bounds.
+    first: A,
+    /// The noise output where the control is within the bounds.
+    second: B,
+    /// Decides which noise is output.
+    control: C,
+    /// The lower bound of the selection range.
+    lower: f32,
+    /// The upper bound of the selection range.
+    upper: f32,
+    /// Half the width of the transitions around the bounds, as requested.
+    /// It is limited to the selection range when evaluating, whatever the
+    /// order the bounds and the falloff are set in.
+    edge_falloff: f32,
+}
+
+impl<A: Noise, B: Noise, C: Noise> Select<A, B, C> {
+    /// Creates a selection between `first` and `second` by `control`,
+    /// the second noise being selected in [-1, 1] with sharp edges.
+    pub fn new(first:A, second:B, control:C) -> Select<A, B, C> {
+        Select {
+            first: first,
+            second: second,
+            control: control,
+            lower: -1.0,
+            upper: 1.0,
+            edge_falloff: 0.0,
+        }
+    }
+
+    /// Sets the bounds of the selection range, given in any order.
+    pub fn set_bounds(&mut self, a:f32, b:f32) {
+        self.lower = a.min(b);
+        self.upper = a.max(b);
+    }
+
+    /// Sets half the width of the transitions around the bounds,
+    /// at most half the width of the selection range.
+    pub fn set_edge_falloff(&mut self, edge_falloff:f32) {
+        self.edge_falloff = edge_falloff;
+    }
+
+    /// Selects between the first and second noises from a value of the
+    /// control. They are only evaluated when they contribute to the output.
+    fn select(&self, control:f32, first: || -> f32, second: || -> f32) -> f32 {
+        // The transitions must not overlap.
+        let half_size = (self.upper - self.lower) / 2.0;
+        let falloff = self.edge_falloff.max(0.0).min(half_size);
+
+        if falloff > 0.0 {
+            if control < self.lower - falloff {
+                first()
+            } else if control < self.lower + falloff {
+                // Blend into the second noise across the lower bound.
+                let t = cubic_curve((control - self.lower + falloff) / (2.0 * falloff));
+                lerp(t, first(), second())
+            } else if control < self.upper - falloff {
+                second()
+            } else if control < self.upper + falloff {
+                // Blend back into the first noise across the upper bound.
+                let t = cubic_curve((control - self.upper + falloff) / (2.0 * falloff));
+                lerp(t, second(), first())
+            } else {
+                first()
+            }
+        } else if control < self.lower || control > self.upper {
+            first()
+        } else {
+            second()
+        }
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<A: Noise, B: Noise, C: Noise> Noise for Select<A, B, C> {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.select(self.control.get_value(x, y, z),
+                    || self.first.get_value(x, y, z),
+                    || self.second.get_value(x, y, z))
+    }
+
+    /// Returns the noise value at the point(x).
+    fn get_1d(&self, x:f32) -> f32 {
+        self.select(self.control.get_1d(x), || self.first.get_1d(x), || self.second.get_1d(x))
+    }
+
+    /// Returns the noise value at the point(x,y).
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.select(self.control.get_2d(x, y),
+                    || self.first.get_2d(x, y),
+                    || self.second.get_2d(x, y))
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<A: Noise4D, B: Noise4D, C: Noise4D> Noise4D for Select<A, B, C> {
+    /// Returns the noise value at the point(x,y,z,w).
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.select(self.control.get_4d(x, y, z, w),
+                    || self.first.get_4d(x, y, z, w),
+                    || self.second.get_4d(x, y, z, w))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noises::perlin::Perlin;
+    use super::Select;
+
+    /// Returns the selection between -1 and 1 by a `control` value,
+    /// in [-0.1, 0.1] with the falloff set before or after the bounds.
+    fn select(control:f32, falloff:f32, falloff_first:bool) -> f32 {
+        let mut select = Select::new(Perlin::new(), Perlin::new(), Perlin::new());
+        if falloff_first {
+            select.set_edge_falloff(falloff);
+            select.set_bounds(0.1, -0.1);
+        } else {
+            select.set_bounds(0.1, -0.1);
+            select.set_edge_falloff(falloff);
+        }
+        select.select(control, || -1.0, || 1.0)
+    }
+
+    #[test]
+    fn sharp_edges_without_falloff() {
+        assert_eq!(select(-0.11, 0.0, false), -1.0);
+        assert_eq!(select(-0.1, 0.0, false), 1.0);
+        assert_eq!(select(0.1, 0.0, false), 1.0);
+        assert_eq!(select(0.11, 0.0, false), -1.0);
+    }
+
+    #[test]
+    fn falloff_blends_across_the_bounds() {
+        // Halfway through the transitions.
+        assert!(select(-0.1, 0.05, false).abs() < 1e-6);
+        assert!(select(0.1, 0.05, false).abs() < 1e-6);
+        assert_eq!(select(-0.15, 0.05, false), -1.0);
+        assert_eq!(select(0.0, 0.05, false), 1.0);
+        assert_eq!(select(0.15, 0.05, false), -1.0);
+    }
+
+    #[test]
+    fn falloff_is_limited_to_the_selection_range() {
+        // A falloff of 0.5 is limited to 0.1, half the width of the range,
+        // whatever the order it is set in: the middle of the range is
+        // still the second noise.
+        for &falloff_first in [true, false].iter() {
+            assert_eq!(select(0.0, 0.5, falloff_first), 1.0);
+            assert!(select(-0.1, 0.5, falloff_first).abs() < 1e-6);
+            assert_eq!(select(-0.2, 0.5, falloff_first), -1.0);
+            assert_eq!(select(0.2, 0.5, falloff_first), -1.0);
+            assert_eq!(select(0.0, 0.5, falloff_first), select(0.0, 0.1, falloff_first));
+        }
+    }
+}
@@ -0,0 +1,723 @@
+//! Text expressions describing noise generators.
+//!
+//! An expression is a compact form of a noise graph, e.g.
+//!
+//! ```text
+//! clamp(ridged(perlin(seed=3, freq=0.01), octaves=6) * 0.7 + billow(p * 2), -1, 1)
+//! ```
+//!
+//! A call names a generator of the graph module, `ridged`, `hybrid` and
+//! `hetero` being short for `ridged_multi`, `hybrid_multi` and
+//! `hetero_terrain`. Its arguments are:
+//!
+//! * named parameters, `name=value`, with a number, a name, `true`,
+//!   `false` or a list of numbers `[a, b, ...]` as value;
+//! * noises, which are the sources of the generator;
+//! * numbers, which are the main parameters of a few generators, e.g. the
+//!   bounds of `clamp` or the exponent of `exponent`, and constant noises
+//!   otherwise;
+//! * first of all, the point the generator is sampled at, built from `p`:
+//!   `billow(p * 2)` is twice as fine as `billow()`.
+//!
+//! Fractals without a source sum octaves of `perlin()`. A fractal samples
+//! its source one octave at a time, so the source only takes the parameters
+//! listed by `graph::basis_params`, and `freq`, which is given to the
+//! fractal unless the fractal has its own.
+//!
+//! Noises and numbers combine with `+`, `-`, `*` and `/`, except that a
+//! noise can not divide. The expression is checked before being built:
+//! unknown generators or parameters, and mixing up points, noises, numbers
+//! and names are errors.
+
+use std::fmt;
+
+use graph::{GraphError, Node, Number, Boolean, Text, Numbers, basis_params, generator_params};
+use noise::Noise;
+
+/// The fractal generators, which sum octaves of a basis noise.
+static FRACTALS: [&'static str, ..5] = ["fbm", "billow", "ridged_multi", "hybrid_multi",
+                                        "hetero_terrain"];
+
+/// What can go wrong when reading an expression. The positions are
+/// byte offsets into the text.
+#[deriving(Clone, PartialEq)]
+pub enum ExpressionError {
+    /// The text is not a valid expression.
+    ParseError(uint, String),
+    /// Values of the wrong type are mixed up, or a generator or a
+    /// parameter is unknown.
+    TypeError(uint, String),
+    /// An operation can not be computed, e.g. a division by zero.
+    EvaluationError(uint, String),
+    /// The described graph is invalid.
+    BuildError(GraphError),
+}
+
+impl fmt::Show for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError(pos, ref message) => write!(f, "at {}: {}", pos, message),
+            TypeError(pos, ref message) => write!(f, "at {}: {}", pos, message),
+            EvaluationError(pos, ref message) => write!(f, "at {}: {}", pos, message),
+            BuildError(ref error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Reads an expression into a noise graph.
+pub fn parse(text:&str) -> Result<Node, ExpressionError> {
+    let tokens = try!(tokenize(text));
+    let mut parser = Parser { tokens: tokens, index: 0 };
+    let expr = try!(parser.parse_expr());
+    let (pos, token) = parser.next();
+    if token != EndToken {
+        return Err(ParseError(pos, "expected an operator or the end of the \
+                                    expression".to_string()));
+    }
+
+    match try!(evaluate(&expr)) {
+        NoiseValue(node) => Ok(node),
+        NumberValue(v) => Ok(constant(v)),
+        other => Err(TypeError(expr.pos, format!("expected a noise, found {}", other.describe()))),
+    }
+}
+
+/// Reads an expression and builds the generator it describes.
+pub fn compile(text:&str) -> Result<Box<Noise>, ExpressionError> {
+    let node = try!(parse(text));
+    match node.build() {
+        Ok(noise) => Ok(noise),
+        Err(e) => Err(BuildError(e)),
+    }
+}
+
+#[deriving(Clone, PartialEq, Show)]
+enum Token {
+    NumberToken(f64),
+    NameToken(String),
+    SymbolToken(char),
+    EndToken,
+}
+
+/// Splits the text into tokens, along with their position.
+fn tokenize(text:&str) -> Result<Vec<(uint, Token)>, ExpressionError> {
+    let bytes = text.as_bytes();
+    let is_digit = |i:uint| i < bytes.len() && (bytes[i] as char).is_digit();
+    let mut tokens = Vec::new();
+    let mut i = 0u;
+
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i] >= 0x80 {
+            return Err(ParseError(i, "unexpected non ASCII character".to_string()));
+        }
+        let c = bytes[i] as char;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_digit() || c == '.' {
+            while is_digit(i) || (i < bytes.len() && bytes[i] as char == '.') {
+                i += 1;
+            }
+            // An optional exponent.
+            if i < bytes.len() && (bytes[i] as char == 'e' || bytes[i] as char == 'E') {
+                i += 1;
+                if i < bytes.len() && (bytes[i] as char == '+' || bytes[i] as char == '-') {
+                    i += 1;
+                }
+                while is_digit(i) {
+                    i += 1;
+                }
+            }
+            let literal = text.slice(start, i);
+            match from_str::<f64>(literal) {
+                Some(v) => tokens.push((start, NumberToken(v))),
+                None => return Err(ParseError(start, format!("invalid number `{}`", literal))),
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while i < bytes.len()
+                  && ((bytes[i] as char).is_alphanumeric() || bytes[i] as char == '_') {
+                i += 1;
+            }
+            tokens.push((start, NameToken(text.slice(start, i).to_string())));
+        } else if "+-*/(),=[]".contains_char(c) {
+            tokens.push((start, SymbolToken(c)));
+            i += 1;
+        } else {
+            return Err(ParseError(start, format!("unexpected `{}`", c)));
+        }
+    }
+    tokens.push((bytes.len(), EndToken));
+    Ok(tokens)
+}
+
+/// An expression, as written.
+struct Expr {
+    /// Where the expression starts, or the position of its operator.
+    pos: uint,
+    kind: ExprKind,
+}
+
+enum ExprKind {
+    NumberLiteral(f64),
+    Name(String),
+    Call(String, Vec<Argument>),
+    BinaryOp(char, Box<Expr>, Box<Expr>),
+    Negate(Box<Expr>),
+    ListLiteral(Vec<Expr>),
+}
+
+/// An argument of a call, named or positional.
+struct Argument {
+    /// Where the argument starts, at its name if it has one.
+    pos: uint,
+    name: Option<String>,
+    value: Expr,
+}
+
+/// Recursive descent parser:
+///
+/// ```text
+/// expr      := term (('+' | '-') term)*
+/// term      := unary (('*' | '/') unary)*
+/// unary     := '-' unary | primary
+/// primary   := number | name | name '(' arguments ')' | '(' expr ')'
+///            | '[' (expr (',' expr)*)? ']'
+/// arguments := (argument (',' argument)*)?
+/// argument  := name '=' expr | expr
+/// ```
+struct Parser {
+    tokens: Vec<(uint, Token)>,
+    index: uint,
+}
+
+impl Parser {
+    /// Returns the token `offset` tokens ahead, without consuming it.
+    fn peek(&self, offset:uint) -> &Token {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        let &(_, ref token) = self.tokens.get(index);
+        token
+    }
+
+    /// Whether the next token is the symbol `c`.
+    fn peek_symbol(&self, c:char) -> bool {
+        *self.peek(0) == SymbolToken(c)
+    }
+
+    /// Consumes the next token.
+    fn next(&mut self) -> (uint, Token) {
+        let token = self.tokens.get(self.index).clone();
+        if self.index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+        token
+    }
+
+    /// Consumes the symbol `c`.
+    fn expect(&mut self, c:char) -> Result<(), ExpressionError> {
+        let (pos, token) = self.next();
+        if token == SymbolToken(c) {
+            Ok(())
+        } else {
+            Err(ParseError(pos, format!("expected `{}`", c)))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = try!(self.parse_term());
+        while self.peek_symbol('+') || self.peek_symbol('-') {
+            let (pos, token) = self.next();
+            let op = match token { SymbolToken(c) => c, _ => unreachable!() };
+            let right = try!(self.parse_term());
+            left = Expr { pos: pos, kind: BinaryOp(op, box left, box right) };
+        }
+        Ok(left)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = try!(self.parse_unary());
+        while self.peek_symbol('*') || self.peek_symbol('/') {
+            let (pos, token) = self.next();
+            let op = match token { SymbolToken(c) => c, _ => unreachable!() };
+            let right = try!(self.parse_unary());
+            left = Expr { pos: pos, kind: BinaryOp(op, box left, box right) };
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.peek_symbol('-') {
+            let (pos, _) = self.next();
+            let operand = try!(self.parse_unary());
+            return Ok(Expr { pos: pos, kind: Negate(box operand) });
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExpressionError> {
+        let (pos, token) = self.next();
+        match token {
+            NumberToken(v) => Ok(Expr { pos: pos, kind: NumberLiteral(v) }),
+            NameToken(name) => {
+                if !self.peek_symbol('(') {
+                    return Ok(Expr { pos: pos, kind: Name(name) });
+                }
+                self.next();
+                let arguments = try!(self.parse_arguments());
+                Ok(Expr { pos: pos, kind: Call(name, arguments) })
+            }
+            SymbolToken('(') => {
+                let expr = try!(self.parse_expr());
+                try!(self.expect(')'));
+                Ok(expr)
+            }
+            SymbolToken('[') => {
+                let mut items = Vec::new();
+                if self.peek_symbol(']') {
+                    self.next();
+                } else {
+                    loop {
+                        items.push(try!(self.parse_expr()));
+                        if self.peek_symbol(']') {
+                            self.next();
+                            break;
+                        }
+                        try!(self.expect(','));
+                    }
+                }
+                Ok(Expr { pos: pos, kind: ListLiteral(items) })
+            }
+            SymbolToken(c) => Err(ParseError(pos, format!("unexpected `{}`", c))),
+            EndToken => Err(ParseError(pos, "unexpected end of the expression".to_string())),
+        }
+    }
+
+    /// Parses the arguments of a call, after its opening parenthesis.
+    fn parse_arguments(&mut self) -> Result<Vec<Argument>, ExpressionError> {
+        let mut arguments = Vec::new();
+        if self.peek_symbol(')') {
+            self.next();
+            return Ok(arguments);
+        }
+        loop {
+            let &(pos, _) = self.tokens.get(self.index);
+            let named = match (self.peek(0), self.peek(1)) {
+                (&NameToken(_), &SymbolToken('=')) => true,
+                _ => false,
+            };
+            let name = if named {
+                let (_, token) = self.next();
+                self.next();
+                match token { NameToken(name) => Some(name), _ => unreachable!() }
+            } else {
+                None
+            };
+            arguments.push(Argument { pos: pos, name: name, value: try!(self.parse_expr()) });
+
+            if self.peek_symbol(')') {
+                self.next();
+                return Ok(arguments);
+            }
+            try!(self.expect(','));
+        }
+    }
+}
+
+/// The value of an expression, whose type is checked.
+enum Value {
+    NumberValue(f64),
+    NoiseValue(Node),
+    /// The point a generator is sampled at, as the operations
+    /// applied to `p`.
+    PointValue(Vec<PointOp>),
+    NameValue(String),
+    BooleanValue(bool),
+    ListValue(Vec<f64>),
+}
+
+enum PointOp {
+    ScaleOp(f64),
+    TranslateOp(f64),
+}
+
+impl Value {
+    /// Describes the type of the value, for the error messages.
+    fn describe(&self) -> String {
+        match *self {
+            NumberValue(_) => "a number".to_string(),
+            NoiseValue(_) => "a noise".to_string(),
+            PointValue(_) => "the point".to_string(),
+            NameValue(ref name) => format!("the name `{}`", name),
+            BooleanValue(_) => "a boolean".to_string(),
+            ListValue(_) => "a list".to_string(),
+        }
+    }
+}
+
+fn evaluate(expr:&Expr) -> Result<Value, ExpressionError> {
+    match expr.kind {
+        NumberLiteral(v) => Ok(NumberValue(v)),
+        Name(ref name) => Ok(match name.as_slice() {
+            "p" => PointValue(Vec::new()),
+            "true" => BooleanValue(true),
+            "false" => BooleanValue(false),
+            _ => NameValue(name.clone()),
+        }),
+        Call(ref name, ref arguments) => call(expr.pos, name.as_slice(), arguments.as_slice()),
+        BinaryOp(op, ref left, ref right) => {
+            let left = try!(evaluate(&**left));
+            let right = try!(evaluate(&**right));
+            binary_op(expr.pos, op, left, right)
+        }
+        Negate(ref operand) => match try!(evaluate(&**operand)) {
+            NumberValue(v) => Ok(NumberValue(-v)),
+            NoiseValue(node) => Ok(NoiseValue(with_sources("invert", vec![node]))),
+            PointValue(mut ops) => {
+                ops.push(ScaleOp(-1.0));
+                Ok(PointValue(ops))
+            }
+            other => Err(TypeError(expr.pos, format!("cannot negate {}", other.describe()))),
+        },
+        ListLiteral(ref items) => {
+            let mut numbers = Vec::new();
+            for item in items.iter() {
+                match try!(evaluate(item)) {
+                    NumberValue(v) => numbers.push(v),
+                    other => return Err(TypeError(item.pos, format!("expected a number in the \
+                                                                     list, found {}",
+                                                                    other.describe()))),
+                }
+            }
+            Ok(ListValue(numbers))
+        }
+    }
+}
+
+fn binary_op(pos:uint, op:char, left:Value, right:Value) -> Result<Value, ExpressionError> {
+    let mismatch = |left:&Value, right:&Value| {
+        Err(TypeError(pos, format!("cannot apply `{}` to {} and {}",
+                                   op, left.describe(), right.describe())))
+    };
+
+    match (left, right) {
+        (NumberValue(a), NumberValue(b)) => match op {
+            '+' => Ok(NumberValue(a + b)),
+            '-' => Ok(NumberValue(a - b)),
+            '*' => Ok(NumberValue(a * b)),
+            _ if b == 0.0 => Err(EvaluationError(pos, "division by zero".to_string())),
+            _ => Ok(NumberValue(a / b)),
+        },
+        (NoiseValue(node), NumberValue(v)) => match op {
+            '+' => Ok(NoiseValue(scale_bias(node, 1.0, v))),
+            '-' => Ok(NoiseValue(scale_bias(node, 1.0, -v))),
+            '*' => Ok(NoiseValue(scale_bias(node, v, 0.0))),
+            _ if v == 0.0 => Err(EvaluationError(pos, "division by zero".to_string())),
+            _ => Ok(NoiseValue(scale_bias(node, 1.0 / v, 0.0))),
+        },
+        (NumberValue(v), NoiseValue(node)) => match op {
+            '+' => Ok(NoiseValue(scale_bias(node, 1.0, v))),
+            '-' => Ok(NoiseValue(scale_bias(node, -1.0, v))),
+            '*' => Ok(NoiseValue(scale_bias(node, v, 0.0))),
+            _ => mismatch(&NumberValue(v), &NoiseValue(node)),
+        },
+        (NoiseValue(a), NoiseValue(b)) => match op {
+            '+' => Ok(NoiseValue(with_sources("add", vec![a, b]))),
+            '-' => {
+                let b = with_sources("invert", vec![b]);
+                Ok(NoiseValue(with_sources("add", vec![a, b])))
+            }
+            '*' => Ok(NoiseValue(with_sources("multiply", vec![a, b]))),
+            _ => mismatch(&NoiseValue(a), &NoiseValue(b)),
+        },
+        (PointValue(mut ops), NumberValue(v)) => {
+            match op {
+                '+' => ops.push(TranslateOp(v)),
+                '-' => ops.push(TranslateOp(-v)),
+                '*' => ops.push(ScaleOp(v)),
+                _ if v == 0.0 => return Err(EvaluationError(pos, "division by zero".to_string())),
+                _ => ops.push(ScaleOp(1.0 / v)),
+            }
+            Ok(PointValue(ops))
+        }
+        (NumberValue(v), PointValue(mut ops)) => {
+            match op {
+                '+' => ops.push(TranslateOp(v)),
+                '*' => ops.push(ScaleOp(v)),
+                _ => return mismatch(&NumberValue(v), &PointValue(ops)),
+            }
+            Ok(PointValue(ops))
+        }
+        (left, right) => mismatch(&left, &right),
+    }
+}
+
+/// Returns the names of the parameters given by the positional numbers
+/// of a generator.
+fn positional_params(kind:&str) -> &'static [&'static str] {
+    static VALUE: [&'static str, ..1] = ["value"];
+    static BOUNDS: [&'static str, ..2] = ["lower", "upper"];
+    static EXPONENT: [&'static str, ..1] = ["exponent"];
+    static SCALE_BIAS: [&'static str, ..2] = ["scale", "bias"];
+    static NONE: [&'static str, ..0] = [];
+    match kind {
+        "const" => VALUE.as_slice(),
+        "clamp" | "select" => BOUNDS.as_slice(),
+        "exponent" => EXPONENT.as_slice(),
+        "scale_bias" => SCALE_BIAS.as_slice(),
+        _ => NONE.as_slice(),
+    }
+}
+
+fn call(pos:uint, name:&str, arguments:&[Argument]) -> Result<Value, ExpressionError> {
+    let kind = match name {
+        "ridged" => "ridged_multi",
+        "hybrid" => "hybrid_multi",
+        "hetero" => "hetero_terrain",
+        _ => name,
+    };
+    let params = match generator_params(kind) {
+        Some(params) => params,
+        None => return Err(TypeError(pos, format!("unknown generator `{}`", name))),
+    };
+    let positional = positional_params(kind);
+    let mut next_positional = 0u;
+    let mut node = Node::new(kind);
+    let mut point = None;
+    // The position of the first source.
+    let mut source_pos = pos;
+
+    for (i, argument) in arguments.iter().enumerate() {
+        let value = try!(evaluate(&argument.value));
+        match argument.name {
+            Some(ref param_name) => {
+                let param = match value {
+                    NumberValue(v) => Number(v),
+                    NameValue(name) => Text(name),
+                    BooleanValue(b) => Boolean(b),
+                    ListValue(numbers) => Numbers(numbers),
+                    other => return Err(TypeError(argument.value.pos,
+                                                  format!("parameter `{}` expects a number, \
+                                                           a name, a boolean or a list, \
+                                                           found {}",
+                                                          param_name, other.describe()))),
+                };
+                let param_name = match param_name.as_slice() {
+                    "freq" => "frequency",
+                    other => other,
+                };
+                if !params.iter().any(|param| *param == param_name) {
+                    return Err(TypeError(argument.pos, format!("`{}` has no parameter `{}`",
+                                                               name, param_name)));
+                }
+                node.set_param(param_name, param);
+            }
+            None => match value {
+                PointValue(ops) => {
+                    if i != 0 {
+                        return Err(TypeError(argument.value.pos,
+                                             "the point must be the first argument".to_string()));
+                    }
+                    point = Some(ops);
+                }
+                NoiseValue(source) => {
+                    if node.sources.is_empty() {
+                        source_pos = argument.value.pos;
+                    }
+                    node.add_source(source);
+                }
+                NumberValue(v) => {
+                    if next_positional < positional.len() {
+                        node.set_param(positional[next_positional], Number(v));
+                        next_positional += 1;
+                    } else {
+                        node.add_source(constant(v));
+                    }
+                }
+                other => return Err(TypeError(argument.value.pos,
+                                              format!("expected a noise or a number, found {}",
+                                                      other.describe()))),
+            },
+        }
+    }
+
+    if FRACTALS.iter().any(|fractal| *fractal == kind) {
+        if node.sources.is_empty() {
+            node.add_source(Node::new("perlin"));
+        }
+        try!(check_basis(source_pos, name, &mut node));
+    }
+
+    // Sample the generator at the transformed point: the first operation
+    // applied to `p` wraps the others.
+    match point {
+        Some(ops) => {
+            for op in ops.iter().rev() {
+                node = match *op {
+                    ScaleOp(v) => with_xyz("scale_point", v, node),
+                    TranslateOp(v) => with_xyz("translate_point", v, node),
+                };
+            }
+        }
+        None => {}
+    }
+    Ok(NoiseValue(node))
+}
+
+/// Checks the parameters of the basis of the fractal `node`, called `name`
+/// at `pos`, and gives the frequency of the basis to the fractal. Sources
+/// which are not a basis are reported when the graph is built.
+fn check_basis(pos:uint, name:&str, node:&mut Node) -> Result<(), ExpressionError> {
+    let params = match basis_params(node.sources.get(0).kind.as_slice()) {
+        Some(params) => params,
+        None => return Ok(()),
+    };
+    let frequency = "frequency".to_string();
+    if node.sources.get(0).params.contains_key(&frequency) {
+        if node.params.contains_key(&frequency) {
+            return Err(TypeError(pos, format!("both `{}` and its basis set the frequency", name)));
+        }
+        let basis_frequency = node.sources.get_mut(0).params.pop(&frequency).unwrap();
+        node.set_param("frequency", basis_frequency);
+    }
+    for param_name in node.sources.get(0).params.keys() {
+        if !params.iter().any(|param| *param == param_name.as_slice()) {
+            return Err(TypeError(pos, format!("the basis of `{}` takes no parameter `{}`",
+                                              name, param_name)));
+        }
+    }
+    Ok(())
+}
+
+/// Returns a node of type `kind` with `sources`.
+fn with_sources(kind:&str, sources:Vec<Node>) -> Node {
+    let mut node = Node::new(kind);
+    for source in sources.move_iter() {
+        node.add_source(source);
+    }
+    node
+}
+
+/// Returns a point transformer of type `kind`, with `v` along each axis.
+fn with_xyz(kind:&str, v:f64, source:Node) -> Node {
+    let mut node = with_sources(kind, vec![source]);
+    node.set_param("x", Number(v));
+    node.set_param("y", Number(v));
+    node.set_param("z", Number(v));
+    node
+}
+
+fn scale_bias(source:Node, scale:f64, bias:f64) -> Node {
+    let mut node = with_sources("scale_bias", vec![source]);
+    node.set_param("scale", Number(scale));
+    node.set_param("bias", Number(bias));
+    node
+}
+
+fn constant(v:f64) -> Node {
+    let mut node = Node::new("const");
+    node.set_param("value", Number(v));
+    node
+}
+
+
+#[cfg(test)]
+mod test {
+    use graph::{Node, Number};
+    use super::{parse, compile, ParseError, TypeError, EvaluationError};
+
+    /// Returns the value of the constant described by `text`.
+    fn number(text:&str) -> f64 {
+        let node = parse(text).unwrap();
+        assert_eq!(node.kind.as_slice(), "const");
+        match node.params.find(&"value".to_string()) {
+            Some(&Number(v)) => v,
+            _ => fail!("`{}` is not a constant", text),
+        }
+    }
+
+    fn param(node:&Node, name:&str) -> f64 {
+        match node.params.find(&name.to_string()) {
+            Some(&Number(v)) => v,
+            _ => fail!("`{}` has no number `{}`", node.kind, name),
+        }
+    }
+
+    /// Returns the kind of the error reported for `text`, and its position.
+    fn error(text:&str) -> (&'static str, uint) {
+        match parse(text) {
+            Err(ParseError(pos, _)) => ("parse", pos),
+            Err(TypeError(pos, _)) => ("type", pos),
+            Err(EvaluationError(pos, _)) => ("evaluation", pos),
+            _ => fail!("`{}` should not parse", text),
+        }
+    }
+
+    #[test]
+    fn builds_the_example() {
+        let text = "clamp(ridged(perlin(seed=3, freq=0.01), octaves=6) * 0.7 + billow(p * 2), -1, 1)";
+        let clamp = parse(text).unwrap();
+        assert_eq!(clamp.kind.as_slice(), "clamp");
+        assert_eq!((param(&clamp, "lower"), param(&clamp, "upper")), (-1.0, 1.0));
+        let add = clamp.sources.get(0);
+        assert_eq!(add.kind.as_slice(), "add");
+        let scaled = add.sources.get(0);
+        assert_eq!(scaled.kind.as_slice(), "scale_bias");
+        assert_eq!(param(scaled, "scale"), 0.7);
+        // The frequency of the basis goes to the fractal.
+        let ridged = scaled.sources.get(0);
+        assert_eq!(ridged.kind.as_slice(), "ridged_multi");
+        assert_eq!((param(ridged, "octaves"), param(ridged, "frequency")), (6.0, 0.01));
+        assert_eq!(param(ridged.sources.get(0), "seed"), 3.0);
+        assert_eq!(add.sources.get(1).kind.as_slice(), "scale_point");
+        assert!(compile(text).is_ok());
+    }
+
+    #[test]
+    fn operators_follow_precedence() {
+        assert_eq!(number("1 + 2 * 3"), 7.0);
+        assert_eq!(number("(1 + 2) * 3"), 9.0);
+        assert_eq!(number("8 / 2 / 2"), 2.0);
+        assert_eq!(number("1 - 2 - 3"), -4.0);
+        assert_eq!(number("-2 * -3 + 1"), 7.0);
+    }
+
+    #[test]
+    fn point_transforms_apply_in_order() {
+        // `perlin(p * 2 + 1)` samples perlin at 2p + 1: the point is
+        // scaled first, then translated.
+        let scale = parse("perlin(p * 2 + 1)").unwrap();
+        assert_eq!(scale.kind.as_slice(), "scale_point");
+        assert_eq!(param(&scale, "x"), 2.0);
+        let translate = scale.sources.get(0);
+        assert_eq!(translate.kind.as_slice(), "translate_point");
+        assert_eq!(param(translate, "x"), 1.0);
+        assert_eq!(translate.sources.get(0).kind.as_slice(), "perlin");
+
+        let scale = parse("billow(-p / 4)").unwrap();
+        assert_eq!(param(&scale, "x"), -1.0);
+        assert_eq!(param(scale.sources.get(0), "x"), 0.25);
+    }
+
+    #[test]
+    fn errors_are_positioned() {
+        assert_eq!(error("foo()"), ("type", 0));
+        assert_eq!(error("fbm(perlin(), octaves=2, bogus=1)"), ("type", 25));
+        assert_eq!(error("perlin() / perlin()"), ("type", 9));
+        assert_eq!(error("perlin(1, p)"), ("type", 10));
+        assert_eq!(error("perlin() / 0"), ("evaluation", 9));
+        assert_eq!(error("1 / (2 - 2)"), ("evaluation", 2));
+        assert_eq!(error("perlin(seed=3"), ("parse", 13));
+        assert_eq!(error("1 + * 2"), ("parse", 4));
+        assert_eq!(error("perlin() $"), ("parse", 9));
+    }
+
+    #[test]
+    fn fractal_bases_take_no_octave_parameters() {
+        assert_eq!(error("ridged(perlin(octaves=3))"), ("type", 7));
+        assert_eq!(error("fbm(value(persistence=0.2))"), ("type", 4));
+        assert_eq!(error("fbm(perlin(freq=2), freq=3)"), ("type", 4));
+        let fbm = parse("fbm(value(freq=2, interpolation=linear, seed=1))").unwrap();
+        assert_eq!(param(&fbm, "frequency"), 2.0);
+        assert!(compile("fbm(value(freq=2, interpolation=linear, seed=1))").is_ok());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,582 @@
+//! The RON and TOML formats of the noise graphs.
+//!
+//! Both are read into, and written from, the JSON layout of the nodes, so
+//! the graphs are checked the same way whatever their format. Only the
+//! parts of RON and TOML describing nodes are supported, see
+//! `graph::Node::from_ron_str` and `graph::Node::from_toml_str`: the
+//! constructs outside of them are reported as `Unsupported`.
+//!
+//! In RON, a node is a struct named after its type, whose fields are its
+//! parameters and its `sources`:
+//!
+//! ```ron
+//! scale_bias(
+//!     scale: 0.5,
+//!     sources: [
+//!         ridged_multi(octaves: 8, sources: [perlin(seed: 3)]),
+//!     ],
+//! )
+//! ```
+//!
+//! In TOML, a node is a table whose `type` key names the generator, its
+//! sources being arrays of tables nested under `sources`:
+//!
+//! ```toml
+//! type = "scale_bias"
+//! scale = 0.5
+//!
+//! [[sources]]
+//! type = "ridged_multi"
+//! octaves = 8
+//!
+//! [[sources.sources]]
+//! type = "perlin"
+//! seed = 3
+//! ```
+
+use std::char;
+use std::collections::TreeMap;
+use std::num::from_str_radix;
+
+use serialize::json::{Json, ToJson};
+
+/// Why a text could not be read.
+pub enum ReadError {
+    /// The text is not valid.
+    Malformed(String),
+    /// The text uses a construct of the format which is not supported.
+    Unsupported(String),
+}
+
+/// Reads the RON text of a graph.
+pub fn read_ron(text:&str) -> Result<Json, ReadError> {
+    let mut s = Scanner::new(text, "//");
+    let node = try!(ron_value(&mut s));
+    s.skip(true);
+    if s.peek().is_some() {
+        return Err(s.unexpected("the end of the text"));
+    }
+    Ok(node)
+}
+
+/// Writes a graph as indented RON text.
+pub fn write_ron(node:&Json) -> String {
+    let mut text = String::new();
+    write_ron_value(&mut text, node, 0);
+    text
+}
+
+/// Reads the TOML text of a graph.
+pub fn read_toml(text:&str) -> Result<Json, ReadError> {
+    let mut s = Scanner::new(text, "#");
+    let mut root = Table::new();
+    // The depth of the table the keys go to.
+    let mut depth = 0u;
+    loop {
+        s.skip(true);
+        match s.peek() {
+            None => break,
+            Some('[') => depth = try!(toml_header(&mut s, &mut root)),
+            Some(_) => {
+                let name = try!(toml_key(&mut s));
+                if name.as_slice() == "sources" {
+                    return Err(s.error("the sources are `[[sources]]` tables"));
+                }
+                s.skip(false);
+                if s.peek() == Some('.') {
+                    return Err(s.unsupported("dotted keys"));
+                }
+                try!(s.expect('='));
+                s.skip(false);
+                let value = try!(toml_value(&mut s));
+                let table = root.last(depth).unwrap();
+                if table.values.contains_key(&name) {
+                    return Err(s.error(format!("duplicate key `{}`", name).as_slice()));
+                }
+                table.values.insert(name, value);
+            }
+        }
+        s.skip(false);
+        match s.peek() {
+            None | Some('\n') => {}
+            Some(_) => return Err(s.unexpected("the end of the line")),
+        }
+    }
+    Ok(root.to_json())
+}
+
+/// Writes a graph as TOML text.
+pub fn write_toml(node:&Json) -> String {
+    let mut text = String::new();
+    write_toml_table(&mut text, node, "sources");
+    text
+}
+
+/// Walks through a text, one character at a time.
+struct Scanner {
+    chars: Vec<char>,
+    pos: uint,
+    /// Starts the comments, which run to the end of the line.
+    comment: &'static str,
+}
+
+impl Scanner {
+    fn new(text:&str, comment:&'static str) -> Scanner {
+        Scanner {
+            chars: text.chars().collect(),
+            pos: 0,
+            comment: comment,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        if self.pos < self.chars.len() {
+            Some(*self.chars.get(self.pos))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `message` prefixed with the current position.
+    fn position(&self, message:&str) -> String {
+        let before = self.chars.slice_to(self.pos);
+        let line = before.iter().filter(|c| **c == '\n').count();
+        let column = before.iter().rev().take_while(|c| **c != '\n').count();
+        format!("line {}, column {}: {}", line + 1, column + 1, message)
+    }
+
+    /// Reports malformed text at the current position.
+    fn error(&self, message:&str) -> ReadError {
+        Malformed(self.position(message))
+    }
+
+    /// Reports an unsupported construct at the current position.
+    fn unsupported(&self, construct:&str) -> ReadError {
+        Unsupported(self.position(format!("{} are not supported", construct).as_slice()))
+    }
+
+    /// Reports that `expected` is missing at the current position, or the
+    /// unsupported construct found there instead.
+    fn unexpected(&self, expected:&str) -> ReadError {
+        if self.at("'") {
+            self.unsupported("single quoted strings and characters")
+        } else if self.at("{") {
+            self.unsupported("inline tables and maps")
+        } else if self.at("/*") {
+            self.unsupported("block comments")
+        } else {
+            self.error(format!("expected {}", expected).as_slice())
+        }
+    }
+
+    fn expect(&mut self, c:char) -> Result<(), ReadError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(format!("`{}`", c).as_slice()))
+        }
+    }
+
+    /// Whether the text continues with `text`.
+    fn at(&self, text:&str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        self.chars.slice_from(self.pos).starts_with(chars.as_slice())
+    }
+
+    fn at_comment(&self) -> bool {
+        self.at(self.comment)
+    }
+
+    /// Skips the spaces and the comments, and the line ends if `newlines`.
+    fn skip(&mut self, newlines:bool) {
+        loop {
+            match self.peek() {
+                Some('\n') if !newlines => return,
+                Some(c) if c.is_whitespace() => self.pos += 1,
+                Some(_) if self.at_comment() => {
+                    while self.peek().is_some() && self.peek() != Some('\n') {
+                        self.pos += 1;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Reads a name made of letters, digits and underscores, which can
+    /// be empty.
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if is_identifier_char(c) => self.pos += 1,
+                _ => break,
+            }
+        }
+        String::from_chars(self.chars.slice(start, self.pos))
+    }
+
+    /// Reads a double quoted string, with JSON escapes.
+    fn string(&mut self) -> Result<String, ReadError> {
+        if self.at("\"\"\"") {
+            return Err(self.unsupported("multi-line strings"));
+        }
+        try!(self.expect('"'));
+        let mut text = String::new();
+        loop {
+            let c = match self.peek() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some(c) => c,
+            };
+            self.pos += 1;
+            match c {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\x08',
+                        Some('f') => '\x0c',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') if self.pos + 5 <= self.chars.len() => {
+                            let hex = String::from_chars(self.chars.slice(self.pos + 1, self.pos + 5));
+                            match from_str_radix::<u32>(hex.as_slice(), 16).and_then(|c| char::from_u32(c)) {
+                                Some(c) => {
+                                    self.pos += 4;
+                                    c
+                                }
+                                None => return Err(self.error("bad unicode escape")),
+                            }
+                        }
+                        _ => return Err(self.error("bad escape")),
+                    };
+                    self.pos += 1;
+                    text.push_char(escaped);
+                }
+                c => text.push_char(c),
+            }
+        }
+    }
+
+    /// Reads a number, digits can be separated by underscores.
+    fn number(&mut self) -> Result<f64, ReadError> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if is_number_char(c) => self.pos += 1,
+                _ => break,
+            }
+        }
+        let text: String = self.chars.slice(start, self.pos).iter()
+            .filter(|c| **c != '_').map(|c| *c).collect();
+        let digits = if text.as_slice().starts_with("+") {
+            text.as_slice().slice_from(1)
+        } else {
+            text.as_slice()
+        };
+        match self.peek() {
+            Some('x') | Some('o') | Some('b') if digits == "0" => {
+                return Err(self.unsupported("hexadecimal, octal and binary numbers"));
+            }
+            _ => {}
+        }
+        match from_str::<f64>(digits) {
+            Some(v) => Ok(v),
+            None => {
+                self.pos = start;
+                Err(self.error("bad number"))
+            }
+        }
+    }
+
+    /// Reads a string, a number or a boolean.
+    fn scalar(&mut self) -> Result<Json, ReadError> {
+        match self.peek() {
+            Some('"') => Ok(try!(self.string()).to_json()),
+            Some(c) if is_number_start(c) => Ok(try!(self.number()).to_json()),
+            Some(c) if is_identifier_char(c) => {
+                let start = self.pos;
+                match self.identifier().as_slice() {
+                    "true" => Ok(true.to_json()),
+                    "false" => Ok(false.to_json()),
+                    "inf" | "nan" => {
+                        self.pos = start;
+                        Err(self.unsupported("infinite and NaN numbers"))
+                    }
+                    _ => {
+                        self.pos = start;
+                        Err(self.error("expected a value"))
+                    }
+                }
+            }
+            _ => Err(self.unexpected("a value")),
+        }
+    }
+
+    /// Reads a list, `item` reading its items.
+    fn list(&mut self, item: |&mut Scanner| -> Result<Json, ReadError>) -> Result<Json, ReadError> {
+        try!(self.expect('['));
+        let mut items = Vec::new();
+        loop {
+            self.skip(true);
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(items.to_json());
+            }
+            items.push(try!(item(self)));
+            self.skip(true);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {}
+                _ => return Err(self.unexpected("`,` or `]`")),
+            }
+        }
+    }
+}
+
+fn is_identifier_char(c:char) -> bool {
+    (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9') || c == '_'
+}
+
+fn is_number_start(c:char) -> bool {
+    (c >= '0' && c <= '9') || c == '-' || c == '+' || c == '.'
+}
+
+fn is_number_char(c:char) -> bool {
+    is_number_start(c) || c == 'e' || c == 'E' || c == '_'
+}
+
+/// Whether `name` can be written without quotes.
+fn is_identifier(name:&str) -> bool {
+    !name.is_empty() && name.chars().all(is_identifier_char)
+}
+
+/// Reads a RON value: a node, a list or a scalar.
+fn ron_value(s:&mut Scanner) -> Result<Json, ReadError> {
+    s.skip(true);
+    match s.peek() {
+        Some('[') => s.list(|s| ron_value(s)),
+        Some('(') => Err(s.unsupported("tuples and unnamed structs")),
+        Some(c) if is_identifier_char(c) && !is_number_start(c) => {
+            let start = s.pos;
+            let kind = s.identifier();
+            match kind.as_slice() {
+                "true" | "false" => {
+                    s.pos = start;
+                    s.scalar()
+                }
+                _ => ron_node(s, kind),
+            }
+        }
+        _ => s.scalar(),
+    }
+}
+
+/// Reads the fields of a RON node of type `kind`.
+fn ron_node(s:&mut Scanner, kind:String) -> Result<Json, ReadError> {
+    let mut object = TreeMap::new();
+    object.insert("type".to_string(), kind.to_json());
+    s.skip(true);
+    try!(s.expect('('));
+    loop {
+        s.skip(true);
+        if s.peek() == Some(')') {
+            s.pos += 1;
+            return Ok(object.to_json());
+        }
+        let name = s.identifier();
+        if name.is_empty() {
+            return Err(s.unexpected("a field name"));
+        }
+        if name.as_slice() == "type" {
+            return Err(s.error("the type of a node is the name of its struct"));
+        }
+        if object.contains_key(&name) {
+            return Err(s.error(format!("duplicate field `{}`", name).as_slice()));
+        }
+        s.skip(true);
+        try!(s.expect(':'));
+        let value = try!(ron_value(s));
+        object.insert(name, value);
+        s.skip(true);
+        match s.peek() {
+            Some(',') => s.pos += 1,
+            Some(')') => {}
+            _ => return Err(s.unexpected("`,` or `)`")),
+        }
+    }
+}
+
+fn indent(text:&mut String, depth:uint) {
+    for _ in range(0, depth) {
+        text.push_str("    ");
+    }
+}
+
+fn write_ron_value(text:&mut String, value:&Json, depth:uint) {
+    match value.as_object() {
+        Some(object) => {
+            let kind = object.find(&"type".to_string()).and_then(|kind| kind.as_string());
+            text.push_str(kind.unwrap_or(""));
+            text.push_str("(\n");
+            let sources = object.find(&"sources".to_string());
+            for (name, value) in object.iter() {
+                if name.as_slice() != "type" && name.as_slice() != "sources" {
+                    write_ron_field(text, name.as_slice(), value, depth + 1);
+                }
+            }
+            match sources {
+                Some(sources) => write_ron_field(text, "sources", sources, depth + 1),
+                None => {}
+            }
+            indent(text, depth);
+            text.push_str(")");
+        }
+        None => match value.as_list() {
+            Some(list) if list.iter().any(|item| item.is_object()) => {
+                text.push_str("[\n");
+                for item in list.iter() {
+                    indent(text, depth + 1);
+                    write_ron_value(text, item, depth + 1);
+                    text.push_str(",\n");
+                }
+                indent(text, depth);
+                text.push_str("]");
+            }
+            _ => text.push_str(format!("{}", value).as_slice()),
+        },
+    }
+}
+
+fn write_ron_field(text:&mut String, name:&str, value:&Json, depth:uint) {
+    indent(text, depth);
+    text.push_str(name);
+    text.push_str(": ");
+    write_ron_value(text, value, depth);
+    text.push_str(",\n");
+}
+
+/// A TOML table being read. Only the `sources` arrays of tables nest.
+struct Table {
+    values: TreeMap<String, Json>,
+    sources: Vec<Table>,
+}
+
+impl Table {
+    fn new() -> Table {
+        Table {
+            values: TreeMap::new(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Returns the table opened last, `depth` levels below this one.
+    fn last<'a>(&'a mut self, depth:uint) -> Option<&'a mut Table> {
+        if depth == 0 {
+            return Some(self);
+        }
+        match self.sources.mut_last() {
+            Some(source) => source.last(depth - 1),
+            None => None,
+        }
+    }
+}
+
+impl ToJson for Table {
+    fn to_json(&self) -> Json {
+        let mut object = self.values.clone();
+        if !self.sources.is_empty() {
+            let sources: Vec<Json> = self.sources.iter().map(|source| source.to_json()).collect();
+            object.insert("sources".to_string(), sources.to_json());
+        }
+        object.to_json()
+    }
+}
+
+/// Reads a bare or quoted TOML key.
+fn toml_key(s:&mut Scanner) -> Result<String, ReadError> {
+    if s.peek() == Some('"') {
+        return s.string();
+    }
+    let key = s.identifier();
+    if key.is_empty() {
+        return Err(s.unexpected("a key"));
+    }
+    Ok(key)
+}
+
+/// Reads a TOML value: a list or a scalar.
+fn toml_value(s:&mut Scanner) -> Result<Json, ReadError> {
+    match s.peek() {
+        Some('[') => s.list(|s| toml_value(s)),
+        _ => s.scalar(),
+    }
+}
+
+/// Reads a `[[sources.sources]]` header, opens the new table,
+/// and returns its depth.
+fn toml_header(s:&mut Scanner, root:&mut Table) -> Result<uint, ReadError> {
+    try!(s.expect('['));
+    if s.peek() != Some('[') {
+        return Err(s.unsupported("tables other than `[[sources]]`"));
+    }
+    s.pos += 1;
+    let mut depth = 0u;
+    loop {
+        s.skip(false);
+        let key = try!(toml_key(s));
+        if key.as_slice() != "sources" {
+            return Err(s.unsupported("tables other than `[[sources]]`"));
+        }
+        depth += 1;
+        s.skip(false);
+        if s.peek() != Some('.') {
+            break;
+        }
+        s.pos += 1;
+    }
+    try!(s.expect(']'));
+    try!(s.expect(']'));
+    match root.last(depth - 1) {
+        Some(parent) => parent.sources.push(Table::new()),
+        None => return Err(s.error("the parent `[[sources]]` table is missing")),
+    }
+    Ok(depth)
+}
+
+fn write_toml_table(text:&mut String, node:&Json, path:&str) {
+    let object = match node.as_object() {
+        Some(object) => object,
+        None => return,
+    };
+    // The type first, for readability.
+    match object.find(&"type".to_string()) {
+        Some(kind) => text.push_str(format!("type = {}\n", kind).as_slice()),
+        None => {}
+    }
+    for (name, value) in object.iter() {
+        if name.as_slice() == "type" || name.as_slice() == "sources" {
+            continue;
+        }
+        if is_identifier(name.as_slice()) {
+            text.push_str(name.as_slice());
+        } else {
+            text.push_str(format!("{}", name.to_json()).as_slice());
+        }
+        text.push_str(format!(" = {}\n", value).as_slice());
+    }
+    match object.find(&"sources".to_string()).and_then(|sources| sources.as_list()) {
+        Some(sources) => {
+            let child_path = format!("{}.sources", path);
+            for source in sources.iter() {
+                text.push_str(format!("\n[[{}]]\n", path).as_slice());
+                write_toml_table(text, source, child_path.as_slice());
+            }
+        }
+        None => {}
+    }
+}
\ No newline at end of file
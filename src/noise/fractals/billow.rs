@@ -0,0 +1,66 @@
+use noise::{BasisNoise, Noise};
+use noises::perlin::Perlin;
+use octaves::{Octaves, abs_range};
+
+/// Billow fractal generator.
+///
+/// Each octave of the basis noise is folded with `2|n| - 1` before being
+/// summed, which gives rounded, puffy lumps: clouds or rolling hills.
+pub struct Billow<N> {
+    /// The basis noise, sampled one octave at a time.
+    source: N,
+    /// The octave parameters.
+    octaves: Octaves,
+}
+
+/// Returns the range of a folded octave of `source`.
+fn folded_range<N: BasisNoise>(source:&N) -> (f32, f32) {
+    let (lower, upper) = abs_range(source.get_range());
+    (lower * 2.0 - 1.0, upper * 2.0 - 1.0)
+}
+
+impl<N: BasisNoise> Billow<N> {
+    /// Creates a billow generator over `source` with default parameters.
+    pub fn new(source:N) -> Billow<N> {
+        // The octaves are folded, so is the range of the basis.
+        let mut octaves = Octaves::new();
+        octaves.set_range(folded_range(&source));
+        Billow {
+            source: source,
+            octaves: octaves,
+        }
+    }
+}
+
+impl_octave_setters!(Billow<N: BasisNoise>, persistence)
+
+impl Billow<Perlin> {
+    /// Creates a billow generator with default parameters
+    /// over a Perlin basis seeded from `seed`.
+    pub fn with_seed(seed:u64) -> Billow<Perlin> {
+        Billow::new(Perlin::with_seed(seed))
+    }
+
+    /// Sets the seed of the basis noise and of the octave offsets,
+    /// then measures the range of the new basis.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.source.set_seed(seed);
+        self.octaves.seed = seed;
+        self.octaves.set_range(folded_range(&self.source));
+    }
+
+    /// Returns the seed of the basis noise.
+    pub fn get_seed(&self) -> u64 {
+        self.source.get_seed()
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<N: BasisNoise> Noise for Billow<N> {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        // Fold each octave before summing it.
+        self.octaves.sum_3d(x, y, z, |x, y, z| self.source.get_basis(x, y, z).abs() * 2.0 - 1.0)
+    }
+}
\ No newline at end of file
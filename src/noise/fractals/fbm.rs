@@ -0,0 +1,63 @@
+use noise::{BasisNoise, Noise};
+use octaves::Octaves;
+
+/// Fractal Brownian motion generator.
+///
+/// Sums octaves of any basis noise, each one at a higher frequency and a
+/// lower amplitude than the previous one. The lower dimensions are slices
+/// of the 3D sum. With a `Perlin` basis and the octaves offset from the
+/// seed of the basis, see `set_octave_seed`, the 3D values are the same as
+/// those of `Perlin` itself.
+pub struct Fbm<N> {
+    /// The basis noise, sampled one octave at a time.
+    source: N,
+    /// The octave parameters.
+    octaves: Octaves,
+}
+
+impl<N: BasisNoise> Fbm<N> {
+    /// Creates a fractal Brownian motion generator over `source`
+    /// with default parameters.
+    pub fn new(source:N) -> Fbm<N> {
+        let mut octaves = Octaves::new();
+        octaves.set_range(source.get_range());
+        Fbm {
+            source: source,
+            octaves: octaves,
+        }
+    }
+}
+
+impl_octave_setters!(Fbm<N: BasisNoise>, persistence)
+
+/// Implements the noise generator common trait.
+impl<N: BasisNoise> Noise for Fbm<N> {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.octaves.sum_3d(x, y, z, |x, y, z| self.source.get_basis(x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::Noise;
+    use noises::perlin::Perlin;
+    use super::Fbm;
+
+    #[test]
+    fn perlin_basis_matches_perlin() {
+        let mut perlin = Perlin::with_seed(5);
+        let mut fbm = Fbm::new(Perlin::with_seed(5));
+        fbm.set_octave_seed(5);
+        for &rotation in [false, true].iter() {
+            perlin.set_octave_rotation(rotation);
+            fbm.set_octave_rotation(rotation);
+            for i in range(0u, 100) {
+                let t = i as f32 * 0.37;
+                let (x, y, z) = (t - 20.0, t * 0.61, -t * 1.3);
+                assert_eq!(fbm.get_value(x, y, z), perlin.get_value(x, y, z));
+            }
+        }
+    }
+}
\ No newline at end of file
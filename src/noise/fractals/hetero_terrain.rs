@@ -0,0 +1,116 @@
+use noise::{BasisNoise, Noise};
+use noises::perlin::Perlin;
+use octaves::{Octaves, product_range};
+
+/// Heterogeneous terrain generator, after Musgrave.
+///
+/// Each octave is scaled by the value summed so far, so the roughness
+/// depends on the altitude: low areas stay smooth, high ones get detailed.
+///
+/// The bounded normalizations map the range the scaled octaves can reach,
+/// which grows quickly with the number of octaves and is much wider than
+/// the range they usually span: with the default `Bounded` normalization
+/// the output stays in [-1, 1] but only covers a small part of it. The
+/// `Raw` sum is mostly positive and grows with the offset.
+///
+/// The altitude is summed from the octaves of the basis, which is why it
+/// has to be a `BasisNoise`. Any other generator can be wrapped in an
+/// `AsBasis`, its value then being scaled as a single octave.
+pub struct HeteroTerrain<N> {
+    /// The basis noise, sampled one octave at a time.
+    source: N,
+    /// The octave parameters, the amplitudes follow the spectral exponent.
+    octaves: Octaves,
+    /// Added to the source value, raises the terrain.
+    offset: f32,
+}
+
+impl<N: BasisNoise> HeteroTerrain<N> {
+    /// Creates a heterogeneous terrain generator over `source`
+    /// with default parameters.
+    pub fn new(source:N) -> HeteroTerrain<N> {
+        let mut octaves = Octaves::new();
+        octaves.exponent = Some(0.25);
+        octaves.set_range(source.get_range());
+        HeteroTerrain {
+            source: source,
+            octaves: octaves,
+            offset: 1.0,
+        }
+    }
+
+    /// Sets the offset added to the source value.
+    pub fn set_offset(&mut self, offset:f32) {
+        self.offset = offset;
+    }
+
+    /// Sets the spectral exponent: the amplitude of an octave is its
+    /// frequency to the power of `-exponent`.
+    pub fn set_exponent(&mut self, exponent:f32) {
+        self.octaves.exponent = Some(exponent);
+    }
+
+    /// Returns the range the sum of the octaves can reach.
+    fn bounds(&self) -> (f32, f32) {
+        let (lowest, highest) = self.octaves.ranges[2];
+        let mut first = true;
+        let mut bounds = (0.0, 0.0);
+        self.octaves.each_amplitude(|amplitude| {
+            let (low, high) = ((lowest + self.offset) * amplitude, (highest + self.offset) * amplitude);
+            bounds = if first {
+                first = false;
+                (low, high)
+            } else {
+                // `value + signal * value` is `value * (1 + signal)`.
+                product_range(bounds, (1.0 + low, 1.0 + high))
+            };
+        });
+        bounds
+    }
+}
+
+impl_octave_setters!(HeteroTerrain<N: BasisNoise>)
+
+impl HeteroTerrain<Perlin> {
+    /// Creates a heterogeneous terrain generator with default parameters
+    /// over a Perlin basis seeded from `seed`.
+    pub fn with_seed(seed:u64) -> HeteroTerrain<Perlin> {
+        HeteroTerrain::new(Perlin::with_seed(seed))
+    }
+
+    /// Sets the seed of the basis noise and of the octave offsets,
+    /// then measures the range of the new basis.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.source.set_seed(seed);
+        self.octaves.seed = seed;
+        self.octaves.set_range(self.source.get_range());
+    }
+
+    /// Returns the seed of the basis noise.
+    pub fn get_seed(&self) -> u64 {
+        self.source.get_seed()
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<N: BasisNoise> Noise for HeteroTerrain<N> {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let mut first = true;
+
+        let value = self.octaves.fold_3d(x, y, z, |value, x, y, z, amplitude| {
+            let signal = (self.source.get_basis(x, y, z) + self.offset) * amplitude;
+            if first {
+                // The first octave sets the base altitude.
+                first = false;
+                signal
+            } else {
+                // Scale the octave by the altitude reached so far.
+                value + signal * value
+            }
+        });
+        let (lower, upper) = self.bounds();
+        self.octaves.normalize_fold(value, lower, upper)
+    }
+}
\ No newline at end of file
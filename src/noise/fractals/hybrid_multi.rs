@@ -0,0 +1,113 @@
+use noise::{BasisNoise, Noise};
+use noises::perlin::Perlin;
+use octaves::{Octaves, product_range};
+
+/// Hybrid multifractal generator, after Musgrave.
+///
+/// The octaves are summed like fBm, but each one is weighted by the
+/// previous ones: where the low octaves are low, the details fade out.
+/// Valleys stay smooth while peaks get rough.
+///
+/// The bounded normalizations map the range the weighted octaves can
+/// reach, which is much wider than the range they usually span: with the
+/// default `Bounded` normalization the output stays in [-1, 1] but only
+/// covers part of it. The `Raw` sum lies around [0, octave_count] with
+/// the default parameters.
+///
+/// The weights are derived from the octaves of the basis, which is why it
+/// has to be a `BasisNoise`. Any other generator can be wrapped in an
+/// `AsBasis`, its value then being weighted as a single octave.
+pub struct HybridMulti<N> {
+    /// The basis noise, sampled one octave at a time.
+    source: N,
+    /// The octave parameters, the amplitudes follow the spectral exponent.
+    octaves: Octaves,
+    /// Added to the source value, raises the weight of the octaves.
+    offset: f32,
+}
+
+impl<N: BasisNoise> HybridMulti<N> {
+    /// Creates a hybrid multifractal generator over `source`
+    /// with default parameters.
+    pub fn new(source:N) -> HybridMulti<N> {
+        let mut octaves = Octaves::new();
+        octaves.exponent = Some(0.25);
+        octaves.set_range(source.get_range());
+        HybridMulti {
+            source: source,
+            octaves: octaves,
+            offset: 0.7,
+        }
+    }
+
+    /// Sets the offset added to the source value.
+    pub fn set_offset(&mut self, offset:f32) {
+        self.offset = offset;
+    }
+
+    /// Sets the spectral exponent: the amplitude of an octave is its
+    /// frequency to the power of `-exponent`.
+    pub fn set_exponent(&mut self, exponent:f32) {
+        self.octaves.exponent = Some(exponent);
+    }
+
+    /// Returns the range the sum of the octaves can reach.
+    fn bounds(&self) -> (f32, f32) {
+        let (lowest, highest) = self.octaves.ranges[2];
+        let mut weight = (1.0, 1.0);
+        let (mut lower, mut upper) = (0.0, 0.0);
+        self.octaves.each_amplitude(|amplitude| {
+            let signal = ((lowest + self.offset) * amplitude, (highest + self.offset) * amplitude);
+            let (low, high) = product_range(weight, signal);
+            lower += low;
+            upper += high;
+            weight = (low.min(1.0), high.min(1.0));
+        });
+        (lower, upper)
+    }
+}
+
+impl_octave_setters!(HybridMulti<N: BasisNoise>)
+
+impl HybridMulti<Perlin> {
+    /// Creates a hybrid multifractal generator with default parameters
+    /// over a Perlin basis seeded from `seed`.
+    pub fn with_seed(seed:u64) -> HybridMulti<Perlin> {
+        HybridMulti::new(Perlin::with_seed(seed))
+    }
+
+    /// Sets the seed of the basis noise and of the octave offsets,
+    /// then measures the range of the new basis.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.source.set_seed(seed);
+        self.octaves.seed = seed;
+        self.octaves.set_range(self.source.get_range());
+    }
+
+    /// Returns the seed of the basis noise.
+    pub fn get_seed(&self) -> u64 {
+        self.source.get_seed()
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<N: BasisNoise> Noise for HybridMulti<N> {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        // The weight given by the previous octaves.
+        let mut weight = 1.0;
+
+        let value = self.octaves.fold_3d(x, y, z, |value, x, y, z, amplitude| {
+            let signal = (self.source.get_basis(x, y, z) + self.offset) * amplitude;
+            let value = value + weight * signal;
+
+            // The next octave is weighted by this one.
+            weight = (weight * signal).min(1.0);
+
+            value
+        });
+        let (lower, upper) = self.bounds();
+        self.octaves.normalize_fold(value, lower, upper)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod billow;
+pub mod fbm;
+pub mod hetero_terrain;
+pub mod hybrid_multi;
+pub mod ridged_multi;
\ No newline at end of file
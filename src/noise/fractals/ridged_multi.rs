@@ -0,0 +1,115 @@
+use noise::{BasisNoise, Noise};
+use noises::perlin::Perlin;
+use octaves::{Octaves, abs_range};
+
+/// Ridged multifractal generator, after Musgrave.
+///
+/// Each octave of the basis noise is folded with `offset - |n|` and squared,
+/// which turns its zero crossings into sharp ridges. The previous octave
+/// weights the next one, so details concentrate on the ridges.
+///
+/// The sum is normalized like fBm's, from the range derived from the range
+/// of the basis, the offset and the amplitudes. The default `Bounded`
+/// normalization brings it into [-1, 1].
+pub struct RidgedMulti<N> {
+    /// The basis noise, sampled one octave at a time.
+    source: N,
+    /// The octave parameters, the amplitudes follow the spectral exponent.
+    octaves: Octaves,
+    /// Raises the folded signal, the ridges get thicker as it grows.
+    offset: f32,
+    /// Controls how much an octave weights the next one.
+    gain: f32,
+}
+
+impl<N: BasisNoise> RidgedMulti<N> {
+    /// Creates a ridged multifractal generator over `source`
+    /// with default parameters.
+    pub fn new(source:N) -> RidgedMulti<N> {
+        let mut octaves = Octaves::new();
+        octaves.exponent = Some(1.0);
+        octaves.set_range(source.get_range());
+        RidgedMulti {
+            source: source,
+            octaves: octaves,
+            offset: 1.0,
+            gain: 2.0,
+        }
+    }
+
+    /// Sets the offset added to the folded signal.
+    pub fn set_offset(&mut self, offset:f32) {
+        self.offset = offset;
+    }
+
+    /// Sets the gain of the weight given by an octave to the next one.
+    pub fn set_gain(&mut self, gain:f32) {
+        self.gain = gain;
+    }
+
+    /// Sets the spectral exponent: the amplitude of an octave is its
+    /// frequency to the power of `-exponent`.
+    pub fn set_exponent(&mut self, exponent:f32) {
+        self.octaves.exponent = Some(exponent);
+    }
+
+    /// Returns the range the sum of the octaves can reach.
+    fn bounds(&self) -> (f32, f32) {
+        // The range of the folded signal `offset - |n|`, then squared.
+        let (lowest, highest) = abs_range(self.octaves.ranges[2]);
+        let (lower, upper) = abs_range((self.offset - highest, self.offset - lowest));
+        // Only the first octave has a full weight, the next ones can be
+        // weighted down to zero.
+        let mut total_amplitude = 0.0;
+        self.octaves.each_amplitude(|amplitude| total_amplitude += amplitude);
+        (lower * lower, upper * upper * total_amplitude)
+    }
+}
+
+impl_octave_setters!(RidgedMulti<N: BasisNoise>)
+
+impl RidgedMulti<Perlin> {
+    /// Creates a ridged multifractal generator with default parameters
+    /// over a Perlin basis seeded from `seed`.
+    pub fn with_seed(seed:u64) -> RidgedMulti<Perlin> {
+        RidgedMulti::new(Perlin::with_seed(seed))
+    }
+
+    /// Sets the seed of the basis noise and of the octave offsets,
+    /// then measures the range of the new basis.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.source.set_seed(seed);
+        self.octaves.seed = seed;
+        self.octaves.set_range(self.source.get_range());
+    }
+
+    /// Returns the seed of the basis noise.
+    pub fn get_seed(&self) -> u64 {
+        self.source.get_seed()
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<N: BasisNoise> Noise for RidgedMulti<N> {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        // The weight given by the previous octave.
+        let mut weight = 1.0;
+
+        let value = self.octaves.fold_3d(x, y, z, |value, x, y, z, amplitude| {
+            // Fold the signal into ridges and sharpen them.
+            let mut signal = self.offset - self.source.get_basis(x, y, z).abs();
+            signal *= signal;
+            // Apply the weight of the previous octave.
+            signal *= weight;
+
+            // Compute the weight of the next octave from this one.
+            weight = (signal * self.gain).max(0.0).min(1.0);
+
+            value + signal * amplitude
+        });
+        let (lower, upper) = self.bounds();
+        self.octaves.normalize_fold(value, lower, upper)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,1126 @@
+//! Declarative description of noise graphs.
+//!
+//! A graph is a tree of nodes, each naming a generator along with its
+//! parameters and its sources. It is read from and written to JSON, RON or
+//! TOML, then built into a boxed `Noise`. In JSON, a node is an object
+//! whose `type` names the generator and whose `sources` lists the source
+//! nodes, the other members being the parameters:
+//!
+//! ```json
+//! {
+//!     "type": "scale_bias",
+//!     "scale": 0.5,
+//!     "sources": [
+//!         { "type": "ridged_multi", "octaves": 8,
+//!           "sources": [ { "type": "perlin", "seed": 3 } ] }
+//!     ]
+//! }
+//! ```
+//!
+//! The same graph in RON, where a node is a struct named after its type:
+//!
+//! ```ron
+//! scale_bias(
+//!     scale: 0.5,
+//!     sources: [
+//!         ridged_multi(octaves: 8, sources: [perlin(seed: 3)]),
+//!     ],
+//! )
+//! ```
+//!
+//! And in TOML, where the sources are nested arrays of tables:
+//!
+//! ```toml
+//! type = "scale_bias"
+//! scale = 0.5
+//!
+//! [[sources]]
+//! type = "ridged_multi"
+//! octaves = 8
+//!
+//! [[sources.sources]]
+//! type = "perlin"
+//! seed = 3
+//! ```
+//!
+//! Only the parts of RON and TOML describing nodes are read, see
+//! `Node::from_ron_str` and `Node::from_toml_str`.
+//!
+//! The fractal nodes sample their source one octave at a time, so it must
+//! be a basis noise: `perlin`, `simplex`, `value`, `worley`,
+//! `open_simplex2` or `open_simplex2s`. The fractal sets the octaves and
+//! the frequency, so its source only takes the parameters listed by
+//! `basis_params`. The seed of a fractal node draws the offsets of its
+//! octaves, while the seed of its source shuffles the basis. Seeds are
+//! read from JSON numbers, which are exact up to 2^53.
+//!
+//! A `rotate_point` node takes either the Euler angles `x`, `y` and `z`,
+//! or a `quaternion` as the list [w, x, y, z].
+//!
+//! The graphs only describe scalar generators: the vector valued ones,
+//! `PerlinVector`, `GradientField`, `ComponentField`, `OffsetField` and
+//! `Curl`, and `DomainWarp` which is driven by them, have no node.
+
+use std::collections::TreeMap;
+use std::fmt;
+
+use serialize::json;
+use serialize::json::{Json, ToJson};
+
+use combiners::add::Add;
+use combiners::blend::Blend;
+use combiners::max::Max;
+use combiners::min::Min;
+use combiners::multiply::Multiply;
+use combiners::power::Power;
+use combiners::select::Select;
+use formats::{ReadError, Malformed, Unsupported, read_ron, read_toml, write_ron, write_toml};
+use fractals::billow::Billow;
+use fractals::fbm::Fbm;
+use fractals::hetero_terrain::HeteroTerrain;
+use fractals::hybrid_multi::HybridMulti;
+use fractals::ridged_multi::RidgedMulti;
+use modifiers::abs::Abs;
+use modifiers::clamp::Clamp;
+use modifiers::curve::Curve;
+use modifiers::exponent::Exponent;
+use modifiers::invert::Invert;
+use modifiers::scale_bias::ScaleBias;
+use modifiers::terrace::Terrace;
+use noise::{BasisNoise, Noise, Normalization, Raw, AmplitudeSum, Bounded, UnitRange};
+use noises::checkerboard::Checkerboard;
+use noises::constant::Const;
+use noises::cylinders::Cylinders;
+use noises::gradient::Gradient;
+use noises::open_simplex::{OpenSimplex2, OpenSimplex2S};
+use noises::perlin::Perlin;
+use noises::simplex::Simplex;
+use noises::spheres::Spheres;
+use noises::value::{Value, Interpolation, Linear, Cubic, Quintic};
+use noises::worley::{Worley, DistanceFunction, Euclidean, Manhattan, Chebyshev, Minkowski};
+use noises::worley::{ReturnType, F1, F2, F2MinusF1, F1TimesF2, CellValue};
+use transformers::displace::Displace;
+use transformers::rotate_point::RotatePoint;
+use transformers::scale_point::ScalePoint;
+use transformers::translate_point::TranslatePoint;
+use transformers::turbulence::Turbulence;
+
+/// The most octaves a node can sum.
+static MAX_OCTAVES: uint = 32;
+
+/// The highest seed, above which JSON numbers are not exact.
+static MAX_SEED: f64 = 9007199254740992.0;
+
+/// Returns the parameters taken by the generator `kind`,
+/// or `None` if there is no such generator.
+pub fn generator_params(kind:&str) -> Option<&'static [&'static str]> {
+    static OCTAVES: [&'static str, ..7] = ["seed", "octaves", "frequency", "persistence",
+                                           "lacunarity", "rotation", "normalization"];
+    static VALUE: [&'static str, ..8] = ["seed", "octaves", "frequency", "persistence",
+                                         "lacunarity", "rotation", "normalization",
+                                         "interpolation"];
+    static WORLEY: [&'static str, ..5] = ["seed", "frequency", "distance", "minkowski_order",
+                                          "return_type"];
+    static RIDGED: [&'static str, ..9] = ["seed", "octaves", "frequency", "lacunarity",
+                                          "rotation", "offset", "gain", "exponent",
+                                          "normalization"];
+    static MULTIFRACTAL: [&'static str, ..8] = ["seed", "octaves", "frequency", "lacunarity",
+                                                "rotation", "offset", "exponent",
+                                                "normalization"];
+    static SEED: [&'static str, ..1] = ["seed"];
+    static CONST: [&'static str, ..1] = ["value"];
+    static FREQUENCY: [&'static str, ..1] = ["frequency"];
+    static GRADIENT: [&'static str, ..2] = ["start", "end"];
+    static BOUNDS: [&'static str, ..2] = ["lower", "upper"];
+    static EXPONENT: [&'static str, ..1] = ["exponent"];
+    static SCALE_BIAS: [&'static str, ..2] = ["scale", "bias"];
+    static CURVE: [&'static str, ..1] = ["points"];
+    static TERRACE: [&'static str, ..2] = ["points", "invert"];
+    static SELECT: [&'static str, ..3] = ["lower", "upper", "edge_falloff"];
+    static XYZ: [&'static str, ..3] = ["x", "y", "z"];
+    static ROTATE: [&'static str, ..4] = ["x", "y", "z", "quaternion"];
+    static TURBULENCE: [&'static str, ..4] = ["seed", "frequency", "power", "roughness"];
+    static NONE: [&'static str, ..0] = [];
+    Some(match kind {
+        "perlin" | "simplex" | "fbm" | "billow" => OCTAVES.as_slice(),
+        "value" => VALUE.as_slice(),
+        "worley" => WORLEY.as_slice(),
+        "open_simplex2" | "open_simplex2s" => SEED.as_slice(),
+        "const" => CONST.as_slice(),
+        "cylinders" | "spheres" => FREQUENCY.as_slice(),
+        "gradient" => GRADIENT.as_slice(),
+        "ridged_multi" => RIDGED.as_slice(),
+        "hybrid_multi" | "hetero_terrain" => MULTIFRACTAL.as_slice(),
+        "clamp" => BOUNDS.as_slice(),
+        "exponent" => EXPONENT.as_slice(),
+        "scale_bias" => SCALE_BIAS.as_slice(),
+        "curve" => CURVE.as_slice(),
+        "terrace" => TERRACE.as_slice(),
+        "select" => SELECT.as_slice(),
+        "scale_point" | "translate_point" => XYZ.as_slice(),
+        "rotate_point" => ROTATE.as_slice(),
+        "turbulence" => TURBULENCE.as_slice(),
+        "checkerboard" | "abs" | "invert" | "add" | "multiply" | "min" | "max" | "power"
+            | "blend" | "displace" => NONE.as_slice(),
+        _ => return None,
+    })
+}
+
+/// Returns the parameters the basis generator `kind` takes as the source
+/// of a fractal, or `None` if it is not a basis noise.
+pub fn basis_params(kind:&str) -> Option<&'static [&'static str]> {
+    static SEED: [&'static str, ..1] = ["seed"];
+    static VALUE: [&'static str, ..2] = ["seed", "interpolation"];
+    static WORLEY: [&'static str, ..4] = ["seed", "distance", "minkowski_order", "return_type"];
+    Some(match kind {
+        "perlin" | "simplex" | "open_simplex2" | "open_simplex2s" => SEED.as_slice(),
+        "value" => VALUE.as_slice(),
+        "worley" => WORLEY.as_slice(),
+        _ => return None,
+    })
+}
+
+/// The value of a parameter.
+#[deriving(Clone, PartialEq, Show)]
+pub enum Param {
+    Number(f64),
+    Boolean(bool),
+    Text(String),
+    Numbers(Vec<f64>),
+}
+
+impl Param {
+    /// Reads a parameter from JSON.
+    fn from_json(json:&Json) -> Option<Param> {
+        match json.as_number() {
+            Some(v) => return Some(Number(v)),
+            None => {}
+        }
+        match json.as_boolean() {
+            Some(v) => return Some(Boolean(v)),
+            None => {}
+        }
+        match json.as_string() {
+            Some(v) => return Some(Text(v.to_string())),
+            None => {}
+        }
+        match json.as_list() {
+            Some(list) => {
+                let mut numbers = Vec::new();
+                for item in list.iter() {
+                    match item.as_number() {
+                        Some(v) => numbers.push(v),
+                        None => return None,
+                    }
+                }
+                Some(Numbers(numbers))
+            }
+            None => None,
+        }
+    }
+}
+
+impl ToJson for Param {
+    fn to_json(&self) -> Json {
+        match *self {
+            Number(v) => v.to_json(),
+            Boolean(v) => v.to_json(),
+            Text(ref v) => v.to_json(),
+            Numbers(ref v) => v.to_json(),
+        }
+    }
+}
+
+/// What can go wrong when reading or building a graph.
+#[deriving(Clone, PartialEq)]
+pub enum GraphError {
+    /// The text is not valid JSON, RON or TOML, or does not describe a node.
+    SyntaxError(String),
+    /// The RON or TOML text uses a construct which is not supported.
+    UnsupportedSyntax(String),
+    /// The node type is unknown.
+    UnknownNode(String),
+    /// The node has a parameter it does not take: node type, parameter.
+    UnknownParameter(String, String),
+    /// A parameter has a wrong value: node type, parameter, expected value.
+    BadParameter(String, String, String),
+    /// The node has a wrong number of sources: node type, expected, found.
+    WrongSourceCount(String, uint, uint),
+    /// A fractal source is not a basis noise: fractal type, source type.
+    NotABasis(String, String),
+}
+
+impl fmt::Show for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SyntaxError(ref message) =>
+                write!(f, "syntax error: {}", message),
+            UnsupportedSyntax(ref message) =>
+                write!(f, "unsupported syntax: {}", message),
+            UnknownNode(ref kind) =>
+                write!(f, "unknown node type `{}`", kind),
+            UnknownParameter(ref kind, ref name) =>
+                write!(f, "`{}` has no parameter `{}`", kind, name),
+            BadParameter(ref kind, ref name, ref expected) =>
+                write!(f, "parameter `{}` of `{}`: expected {}", name, kind, expected),
+            WrongSourceCount(ref kind, expected, found) =>
+                write!(f, "`{}` takes {} source(s), found {}", kind, expected, found),
+            NotABasis(ref kind, ref source) =>
+                write!(f, "the source of `{}` must be a basis noise, found `{}`", kind, source),
+        }
+    }
+}
+
+/// A node of a noise graph.
+#[deriving(Clone, PartialEq, Show)]
+pub struct Node {
+    /// The type of generator, e.g. `perlin`.
+    pub kind: String,
+    /// The parameters of the generator, the others keep their defaults.
+    pub params: TreeMap<String, Param>,
+    /// The source nodes.
+    pub sources: Vec<Node>,
+}
+
+impl Node {
+    /// Creates a node of type `kind`, without parameters nor sources.
+    pub fn new(kind:&str) -> Node {
+        Node {
+            kind: kind.to_string(),
+            params: TreeMap::new(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Sets the parameter `name`.
+    pub fn set_param(&mut self, name:&str, value:Param) {
+        self.params.insert(name.to_string(), value);
+    }
+
+    /// Adds a source node.
+    pub fn add_source(&mut self, source:Node) {
+        self.sources.push(source);
+    }
+
+    /// Reads a graph from JSON text.
+    pub fn from_json_str(text:&str) -> Result<Node, GraphError> {
+        match json::from_str(text) {
+            Ok(json) => Node::from_json(&json),
+            Err(e) => Err(SyntaxError(format!("{}", e))),
+        }
+    }
+
+    /// Reads a graph from JSON.
+    pub fn from_json(json:&Json) -> Result<Node, GraphError> {
+        let object = match json.as_object() {
+            Some(object) => object,
+            None => return Err(SyntaxError("a node must be an object".to_string())),
+        };
+        let kind = match object.find(&"type".to_string()).and_then(|kind| kind.as_string()) {
+            Some(kind) => kind,
+            None => return Err(SyntaxError("a node must have a `type` string".to_string())),
+        };
+
+        let mut node = Node::new(kind);
+        for (name, value) in object.iter() {
+            match name.as_slice() {
+                "type" => {}
+                "sources" => {
+                    let sources = match value.as_list() {
+                        Some(sources) => sources,
+                        None => return Err(BadParameter(node.kind.clone(), name.clone(),
+                                                        "a list of nodes".to_string())),
+                    };
+                    for source in sources.iter() {
+                        node.add_source(try!(Node::from_json(source)));
+                    }
+                }
+                _ => match Param::from_json(value) {
+                    Some(param) => node.set_param(name.as_slice(), param),
+                    None => return Err(BadParameter(node.kind.clone(), name.clone(),
+                                                    "a number, a boolean, a string \
+                                                     or a list of numbers".to_string())),
+                },
+            }
+        }
+        Ok(node)
+    }
+
+    /// Writes the graph as indented JSON text.
+    pub fn to_json_str(&self) -> String {
+        self.to_json().to_pretty_str()
+    }
+
+    /// Reads a graph from RON text.
+    ///
+    /// A node is a struct named after its type, e.g. `perlin(seed: 3)`.
+    /// Its fields are numbers, booleans, double quoted strings, lists of
+    /// numbers, and the list of nodes `sources`. Line comments and trailing
+    /// commas are allowed. Block comments, characters, raw strings, maps,
+    /// tuples and unnamed structs are reported as `UnsupportedSyntax`.
+    pub fn from_ron_str(text:&str) -> Result<Node, GraphError> {
+        match read_ron(text) {
+            Ok(json) => Node::from_json(&json),
+            Err(error) => Err(read_error(error)),
+        }
+    }
+
+    /// Writes the graph as indented RON text.
+    pub fn to_ron_str(&self) -> String {
+        write_ron(&self.to_json())
+    }
+
+    /// Reads a graph from TOML text.
+    ///
+    /// A node is a table of `key = value` lines, with bare or double
+    /// quoted keys, whose `type` key names the generator. The values are
+    /// numbers, booleans, double quoted strings and arrays of numbers, and
+    /// the sources are the nested `[[sources]]`, `[[sources.sources]]`...
+    /// arrays of tables. Comments are allowed. Literal and multi-line
+    /// strings, inline tables, dotted keys, other tables, infinite and NaN
+    /// numbers and non decimal integers are reported as `UnsupportedSyntax`.
+    pub fn from_toml_str(text:&str) -> Result<Node, GraphError> {
+        match read_toml(text) {
+            Ok(json) => Node::from_json(&json),
+            Err(error) => Err(read_error(error)),
+        }
+    }
+
+    /// Writes the graph as TOML text.
+    pub fn to_toml_str(&self) -> String {
+        write_toml(&self.to_json())
+    }
+
+    /// Builds the generator described by the graph.
+    pub fn build(&self) -> Result<Box<Noise>, GraphError> {
+        match self.kind.as_slice() {
+            "perlin" => boxed(try!(self.build_perlin())),
+            "simplex" => boxed(try!(self.build_simplex())),
+            "value" => boxed(try!(self.build_value())),
+            "worley" => boxed(try!(self.build_worley())),
+            "open_simplex2" => boxed(try!(self.build_open_simplex2())),
+            "open_simplex2s" => boxed(try!(self.build_open_simplex2s())),
+
+            "const" => {
+                let r = try!(Reader::new(self, 0));
+                boxed(Const::new(try!(r.float("value", 0.0))))
+            }
+            "checkerboard" => {
+                try!(Reader::new(self, 0));
+                boxed(Checkerboard::new())
+            }
+            "cylinders" => {
+                let r = try!(Reader::new(self, 0));
+                let mut cylinders = Cylinders::new();
+                cylinders.set_frequency(try!(r.float("frequency", 1.0)));
+                boxed(cylinders)
+            }
+            "spheres" => {
+                let r = try!(Reader::new(self, 0));
+                let mut spheres = Spheres::new();
+                spheres.set_frequency(try!(r.float("frequency", 1.0)));
+                boxed(spheres)
+            }
+            "gradient" => {
+                let r = try!(Reader::new(self, 0));
+                let mut gradient = Gradient::new();
+                gradient.set_points(try!(r.point("start", [0.0, 0.0, 0.0])),
+                                    try!(r.point("end", [1.0, 0.0, 0.0])));
+                boxed(gradient)
+            }
+
+            "fbm" => {
+                let r = try!(Reader::new(self, 1));
+                let mut fbm = Fbm::new(try!(r.basis(0)));
+                fbm.set_octave_seed(try!(r.seed()));
+                fbm.set_octave_count(try!(r.count("octaves", 6)));
+                fbm.set_frequency(try!(r.float("frequency", 1.0)));
+                fbm.set_persistence(try!(r.float("persistence", 0.5)));
+                fbm.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+                fbm.set_octave_rotation(try!(r.boolean("rotation", false)));
+                fbm.set_normalization(try!(r.normalization()));
+                boxed(fbm)
+            }
+            "billow" => {
+                let r = try!(Reader::new(self, 1));
+                let mut billow = Billow::new(try!(r.basis(0)));
+                billow.set_octave_seed(try!(r.seed()));
+                billow.set_octave_count(try!(r.count("octaves", 6)));
+                billow.set_frequency(try!(r.float("frequency", 1.0)));
+                billow.set_persistence(try!(r.float("persistence", 0.5)));
+                billow.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+                billow.set_octave_rotation(try!(r.boolean("rotation", false)));
+                billow.set_normalization(try!(r.normalization()));
+                boxed(billow)
+            }
+            "ridged_multi" => {
+                let r = try!(Reader::new(self, 1));
+                let mut ridged = RidgedMulti::new(try!(r.basis(0)));
+                ridged.set_octave_seed(try!(r.seed()));
+                ridged.set_octave_count(try!(r.count("octaves", 6)));
+                ridged.set_frequency(try!(r.float("frequency", 1.0)));
+                ridged.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+                ridged.set_octave_rotation(try!(r.boolean("rotation", false)));
+                ridged.set_offset(try!(r.float("offset", 1.0)));
+                ridged.set_gain(try!(r.float("gain", 2.0)));
+                ridged.set_exponent(try!(r.float("exponent", 1.0)));
+                ridged.set_normalization(try!(r.normalization()));
+                boxed(ridged)
+            }
+            "hybrid_multi" => {
+                let r = try!(Reader::new(self, 1));
+                let mut hybrid = HybridMulti::new(try!(r.basis(0)));
+                hybrid.set_octave_seed(try!(r.seed()));
+                hybrid.set_octave_count(try!(r.count("octaves", 6)));
+                hybrid.set_frequency(try!(r.float("frequency", 1.0)));
+                hybrid.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+                hybrid.set_octave_rotation(try!(r.boolean("rotation", false)));
+                hybrid.set_offset(try!(r.float("offset", 0.7)));
+                hybrid.set_exponent(try!(r.float("exponent", 0.25)));
+                hybrid.set_normalization(try!(r.normalization()));
+                boxed(hybrid)
+            }
+            "hetero_terrain" => {
+                let r = try!(Reader::new(self, 1));
+                let mut terrain = HeteroTerrain::new(try!(r.basis(0)));
+                terrain.set_octave_seed(try!(r.seed()));
+                terrain.set_octave_count(try!(r.count("octaves", 6)));
+                terrain.set_frequency(try!(r.float("frequency", 1.0)));
+                terrain.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+                terrain.set_octave_rotation(try!(r.boolean("rotation", false)));
+                terrain.set_offset(try!(r.float("offset", 1.0)));
+                terrain.set_exponent(try!(r.float("exponent", 0.25)));
+                terrain.set_normalization(try!(r.normalization()));
+                boxed(terrain)
+            }
+
+            "abs" => {
+                let r = try!(Reader::new(self, 1));
+                boxed(Abs::new(try!(r.source(0))))
+            }
+            "clamp" => {
+                let r = try!(Reader::new(self, 1));
+                let mut clamp = Clamp::new(try!(r.source(0)));
+                clamp.set_bounds(try!(r.float("lower", -1.0)), try!(r.float("upper", 1.0)));
+                boxed(clamp)
+            }
+            "exponent" => {
+                let r = try!(Reader::new(self, 1));
+                let mut exponent = Exponent::new(try!(r.source(0)));
+                exponent.set_exponent(try!(r.float("exponent", 1.0)));
+                boxed(exponent)
+            }
+            "invert" => {
+                let r = try!(Reader::new(self, 1));
+                boxed(Invert::new(try!(r.source(0))))
+            }
+            "scale_bias" => {
+                let r = try!(Reader::new(self, 1));
+                let mut scale_bias = ScaleBias::new(try!(r.source(0)));
+                scale_bias.set_scale(try!(r.float("scale", 1.0)));
+                scale_bias.set_bias(try!(r.float("bias", 0.0)));
+                boxed(scale_bias)
+            }
+            "curve" => {
+                let r = try!(Reader::new(self, 1));
+                let points = try!(r.numbers("points"));
+                if points.len() % 2 != 0 {
+                    return Err(r.error("points", "a list of (input, output) pairs"));
+                }
+                let mut curve = Curve::new(try!(r.source(0)));
+                for pair in points.as_slice().chunks(2) {
+                    curve.add_control_point(pair[0] as f32, pair[1] as f32);
+                }
+                boxed(curve)
+            }
+            "terrace" => {
+                let r = try!(Reader::new(self, 1));
+                let mut terrace = Terrace::new(try!(r.source(0)));
+                for point in try!(r.numbers("points")).iter() {
+                    terrace.add_control_point(*point as f32);
+                }
+                terrace.set_invert(try!(r.boolean("invert", false)));
+                boxed(terrace)
+            }
+
+            "add" => {
+                let r = try!(Reader::new(self, 2));
+                boxed(Add::new(try!(r.source(0)), try!(r.source(1))))
+            }
+            "multiply" => {
+                let r = try!(Reader::new(self, 2));
+                boxed(Multiply::new(try!(r.source(0)), try!(r.source(1))))
+            }
+            "min" => {
+                let r = try!(Reader::new(self, 2));
+                boxed(Min::new(try!(r.source(0)), try!(r.source(1))))
+            }
+            "max" => {
+                let r = try!(Reader::new(self, 2));
+                boxed(Max::new(try!(r.source(0)), try!(r.source(1))))
+            }
+            "power" => {
+                let r = try!(Reader::new(self, 2));
+                boxed(Power::new(try!(r.source(0)), try!(r.source(1))))
+            }
+            "blend" => {
+                let r = try!(Reader::new(self, 3));
+                boxed(Blend::new(try!(r.source(0)), try!(r.source(1)), try!(r.source(2))))
+            }
+            "select" => {
+                let r = try!(Reader::new(self, 3));
+                let mut select = Select::new(try!(r.source(0)), try!(r.source(1)),
+                                             try!(r.source(2)));
+                select.set_bounds(try!(r.float("lower", -1.0)), try!(r.float("upper", 1.0)));
+                select.set_edge_falloff(try!(r.float("edge_falloff", 0.0)));
+                boxed(select)
+            }
+
+            "scale_point" => {
+                let r = try!(Reader::new(self, 1));
+                let mut scale = ScalePoint::new(try!(r.source(0)));
+                scale.set_scale(try!(r.float("x", 1.0)),
+                                try!(r.float("y", 1.0)),
+                                try!(r.float("z", 1.0)));
+                boxed(scale)
+            }
+            "translate_point" => {
+                let r = try!(Reader::new(self, 1));
+                let mut translate = TranslatePoint::new(try!(r.source(0)));
+                translate.set_translation(try!(r.float("x", 0.0)),
+                                          try!(r.float("y", 0.0)),
+                                          try!(r.float("z", 0.0)));
+                boxed(translate)
+            }
+            "rotate_point" => {
+                let r = try!(Reader::new(self, 1));
+                let mut rotate = RotatePoint::new(try!(r.source(0)));
+                match r.param("quaternion") {
+                    None => rotate.set_angles(try!(r.float("x", 0.0)),
+                                              try!(r.float("y", 0.0)),
+                                              try!(r.float("z", 0.0))),
+                    Some(&Numbers(ref q)) if q.len() == 4 => {
+                        if r.param("x").is_some() || r.param("y").is_some() || r.param("z").is_some() {
+                            return Err(r.error("quaternion", "no quaternion along with angles"));
+                        }
+                        rotate.set_quaternion(*q.get(0) as f32, *q.get(1) as f32,
+                                              *q.get(2) as f32, *q.get(3) as f32);
+                    }
+                    Some(_) => return Err(r.error("quaternion", "a list of 4 numbers: w, x, y, z")),
+                }
+                boxed(rotate)
+            }
+            "displace" => {
+                let r = try!(Reader::new(self, 4));
+                boxed(Displace::new(try!(r.source(0)), try!(r.source(1)),
+                                    try!(r.source(2)), try!(r.source(3))))
+            }
+            "turbulence" => {
+                let r = try!(Reader::new(self, 1));
+                let mut turbulence = Turbulence::new(try!(r.source(0)));
+                turbulence.set_seed(try!(r.seed()));
+                turbulence.set_frequency(try!(r.float("frequency", 1.0)));
+                turbulence.set_power(try!(r.float("power", 1.0)));
+                turbulence.set_roughness(try!(r.count("roughness", 3)));
+                boxed(turbulence)
+            }
+
+            _ => Err(UnknownNode(self.kind.clone())),
+        }
+    }
+
+    /// Builds the basis noise described by the node, to be the source
+    /// of a fractal. It only takes the parameters listed by `basis_params`,
+    /// the fractal sets the others.
+    pub fn build_basis(&self) -> Result<Box<BasisNoise>, GraphError> {
+        let params = match basis_params(self.kind.as_slice()) {
+            Some(params) => params,
+            None => return Err(UnknownNode(self.kind.clone())),
+        };
+        try!(Reader::new(self, 0));
+        for name in self.params.keys() {
+            if !params.iter().any(|param| *param == name.as_slice()) {
+                return Err(BadParameter(self.kind.clone(), name.clone(),
+                                        "no value on the source of a fractal".to_string()));
+            }
+        }
+        match self.kind.as_slice() {
+            "perlin" => boxed_basis(try!(self.build_perlin())),
+            "simplex" => boxed_basis(try!(self.build_simplex())),
+            "value" => boxed_basis(try!(self.build_value())),
+            "worley" => boxed_basis(try!(self.build_worley())),
+            "open_simplex2" => boxed_basis(try!(self.build_open_simplex2())),
+            "open_simplex2s" => boxed_basis(try!(self.build_open_simplex2s())),
+            _ => Err(UnknownNode(self.kind.clone())),
+        }
+    }
+
+    fn build_perlin(&self) -> Result<Perlin, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        let mut perlin = Perlin::with_seed(try!(r.seed()));
+        perlin.set_octave_count(try!(r.count("octaves", 6)));
+        perlin.set_frequency(try!(r.float("frequency", 1.0)));
+        perlin.set_persistence(try!(r.float("persistence", 0.5)));
+        perlin.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+        perlin.set_octave_rotation(try!(r.boolean("rotation", false)));
+        perlin.set_normalization(try!(r.normalization()));
+        Ok(perlin)
+    }
+
+    fn build_simplex(&self) -> Result<Simplex, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        let mut simplex = Simplex::with_seed(try!(r.seed()));
+        simplex.set_octave_count(try!(r.count("octaves", 6)));
+        simplex.set_frequency(try!(r.float("frequency", 1.0)));
+        simplex.set_persistence(try!(r.float("persistence", 0.5)));
+        simplex.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+        simplex.set_octave_rotation(try!(r.boolean("rotation", false)));
+        simplex.set_normalization(try!(r.normalization()));
+        Ok(simplex)
+    }
+
+    fn build_value(&self) -> Result<Value, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        let mut value = Value::with_seed(try!(r.seed()));
+        value.set_octave_count(try!(r.count("octaves", 6)));
+        value.set_frequency(try!(r.float("frequency", 1.0)));
+        value.set_persistence(try!(r.float("persistence", 0.5)));
+        value.set_lacuranity(try!(r.float("lacunarity", 2.0)));
+        value.set_octave_rotation(try!(r.boolean("rotation", false)));
+        value.set_normalization(try!(r.normalization()));
+        value.set_interpolation(try!(r.interpolation()));
+        Ok(value)
+    }
+
+    fn build_worley(&self) -> Result<Worley, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        let mut worley = Worley::with_seed(try!(r.seed()));
+        worley.set_frequency(try!(r.float("frequency", 1.0)));
+        worley.set_distance_function(try!(r.distance_function()));
+        worley.set_return_type(try!(r.return_type()));
+        Ok(worley)
+    }
+
+    fn build_open_simplex2(&self) -> Result<OpenSimplex2, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        Ok(OpenSimplex2::with_seed(try!(r.seed())))
+    }
+
+    fn build_open_simplex2s(&self) -> Result<OpenSimplex2S, GraphError> {
+        let r = try!(Reader::new(self, 0));
+        Ok(OpenSimplex2S::with_seed(try!(r.seed())))
+    }
+}
+
+impl ToJson for Node {
+    fn to_json(&self) -> Json {
+        let mut object = TreeMap::new();
+        object.insert("type".to_string(), self.kind.to_json());
+        for (name, value) in self.params.iter() {
+            object.insert(name.clone(), value.to_json());
+        }
+        if !self.sources.is_empty() {
+            object.insert("sources".to_string(), self.sources.to_json());
+        }
+        object.to_json()
+    }
+}
+
+/// Converts the errors of the RON and TOML readers.
+fn read_error(error:ReadError) -> GraphError {
+    match error {
+        Malformed(message) => SyntaxError(message),
+        Unsupported(message) => UnsupportedSyntax(message),
+    }
+}
+
+/// Boxes a built generator.
+fn boxed<N: Noise + Send>(noise:N) -> Result<Box<Noise>, GraphError> {
+    Ok(box noise as Box<Noise>)
+}
+
+/// Boxes a built basis noise.
+fn boxed_basis<N: BasisNoise + Send>(basis:N) -> Result<Box<BasisNoise>, GraphError> {
+    Ok(box basis as Box<BasisNoise>)
+}
+
+/// Reads the parameters and sources of a node, checking them.
+struct Reader<'a> {
+    node: &'a Node,
+}
+
+impl<'a> Reader<'a> {
+    /// Checks that `node` only has the parameters of its generator,
+    /// and has `source_count` sources.
+    fn new(node:&'a Node, source_count:uint) -> Result<Reader<'a>, GraphError> {
+        let params = match generator_params(node.kind.as_slice()) {
+            Some(params) => params,
+            None => return Err(UnknownNode(node.kind.clone())),
+        };
+        for name in node.params.keys() {
+            if !params.iter().any(|param| *param == name.as_slice()) {
+                return Err(UnknownParameter(node.kind.clone(), name.clone()));
+            }
+        }
+        if node.sources.len() != source_count {
+            return Err(WrongSourceCount(node.kind.clone(), source_count, node.sources.len()));
+        }
+        Ok(Reader { node: node })
+    }
+
+    fn error(&self, name:&str, expected:&str) -> GraphError {
+        BadParameter(self.node.kind.clone(), name.to_string(), expected.to_string())
+    }
+
+    fn param(&self, name:&str) -> Option<&'a Param> {
+        self.node.params.find(&name.to_string())
+    }
+
+    fn number(&self, name:&str, default:f64) -> Result<f64, GraphError> {
+        match self.param(name) {
+            None => Ok(default),
+            Some(&Number(v)) => Ok(v),
+            Some(_) => Err(self.error(name, "a number")),
+        }
+    }
+
+    fn float(&self, name:&str, default:f32) -> Result<f32, GraphError> {
+        Ok(try!(self.number(name, default as f64)) as f32)
+    }
+
+    /// Reads a number of octaves, up to `MAX_OCTAVES`.
+    fn count(&self, name:&str, default:uint) -> Result<uint, GraphError> {
+        let v = try!(self.number(name, default as f64));
+        if v < 0.0 || v.fract() != 0.0 || v > MAX_OCTAVES as f64 {
+            return Err(self.error(name, format!("an integer from 0 to {}", MAX_OCTAVES).as_slice()));
+        }
+        Ok(v as uint)
+    }
+
+    fn seed(&self) -> Result<u64, GraphError> {
+        let v = try!(self.number("seed", 0.0));
+        if v < 0.0 || v.fract() != 0.0 || v > MAX_SEED {
+            return Err(self.error("seed", "an integer from 0 to 2^53"));
+        }
+        Ok(v as u64)
+    }
+
+    fn boolean(&self, name:&str, default:bool) -> Result<bool, GraphError> {
+        match self.param(name) {
+            None => Ok(default),
+            Some(&Boolean(v)) => Ok(v),
+            Some(_) => Err(self.error(name, "a boolean")),
+        }
+    }
+
+    fn text(&self, name:&str, default:&str) -> Result<String, GraphError> {
+        match self.param(name) {
+            None => Ok(default.to_string()),
+            Some(&Text(ref v)) => Ok(v.clone()),
+            Some(_) => Err(self.error(name, "a string")),
+        }
+    }
+
+    fn numbers(&self, name:&str) -> Result<Vec<f64>, GraphError> {
+        match self.param(name) {
+            None => Ok(Vec::new()),
+            Some(&Numbers(ref v)) => Ok(v.clone()),
+            Some(_) => Err(self.error(name, "a list of numbers")),
+        }
+    }
+
+    fn point(&self, name:&str, default:[f32, ..3]) -> Result<[f32, ..3], GraphError> {
+        match self.param(name) {
+            None => Ok(default),
+            Some(&Numbers(ref v)) if v.len() == 3 => {
+                Ok([*v.get(0) as f32, *v.get(1) as f32, *v.get(2) as f32])
+            }
+            Some(_) => Err(self.error(name, "a list of 3 numbers")),
+        }
+    }
+
+    fn normalization(&self) -> Result<Normalization, GraphError> {
+        let text = try!(self.text("normalization", "bounded"));
+        match text.as_slice() {
+            "raw" => Ok(Raw),
+            "amplitude_sum" => Ok(AmplitudeSum),
+            "bounded" => Ok(Bounded),
+            "unit_range" => Ok(UnitRange),
+            _ => Err(self.error("normalization",
+                                "`raw`, `amplitude_sum`, `bounded` or `unit_range`")),
+        }
+    }
+
+    fn interpolation(&self) -> Result<Interpolation, GraphError> {
+        let text = try!(self.text("interpolation", "quintic"));
+        match text.as_slice() {
+            "linear" => Ok(Linear),
+            "cubic" => Ok(Cubic),
+            "quintic" => Ok(Quintic),
+            _ => Err(self.error("interpolation", "`linear`, `cubic` or `quintic`")),
+        }
+    }
+
+    fn distance_function(&self) -> Result<DistanceFunction, GraphError> {
+        let text = try!(self.text("distance", "euclidean"));
+        if text.as_slice() != "minkowski" && self.param("minkowski_order").is_some() {
+            return Err(self.error("minkowski_order", "no order unless `distance` is `minkowski`"));
+        }
+        match text.as_slice() {
+            "euclidean" => Ok(Euclidean),
+            "manhattan" => Ok(Manhattan),
+            "chebyshev" => Ok(Chebyshev),
+            "minkowski" => {
+                let order = try!(self.float("minkowski_order", 2.0));
+                if !(order > 0.0) {
+                    return Err(self.error("minkowski_order", "a positive number"));
+                }
+                Ok(Minkowski(order))
+            }
+            _ => Err(self.error("distance",
+                                "`euclidean`, `manhattan`, `chebyshev` or `minkowski`")),
+        }
+    }
+
+    fn return_type(&self) -> Result<ReturnType, GraphError> {
+        let text = try!(self.text("return_type", "f1"));
+        match text.as_slice() {
+            "f1" => Ok(F1),
+            "f2" => Ok(F2),
+            "f2_minus_f1" => Ok(F2MinusF1),
+            "f1_times_f2" => Ok(F1TimesF2),
+            "cell_value" => Ok(CellValue),
+            _ => Err(self.error("return_type", "`f1`, `f2`, `f2_minus_f1`, \
+                                               `f1_times_f2` or `cell_value`")),
+        }
+    }
+
+    /// Builds the source `i`.
+    fn source(&self, i:uint) -> Result<Box<Noise>, GraphError> {
+        self.node.sources.get(i).build()
+    }
+
+    /// Builds the source `i` as a basis noise.
+    fn basis(&self, i:uint) -> Result<Box<BasisNoise>, GraphError> {
+        let source = self.node.sources.get(i);
+        match source.build_basis() {
+            Err(UnknownNode(_)) => Err(NotABasis(self.node.kind.clone(), source.kind.clone())),
+            result => result,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::{Node, Number, Boolean, Text, Numbers};
+    use super::{GraphError, SyntaxError, UnsupportedSyntax, BadParameter};
+
+    /// A graph using every kind of parameter.
+    fn graph() -> Node {
+        let mut perlin = Node::new("perlin");
+        perlin.set_param("seed", Number(3.0));
+        let mut ridged = Node::new("ridged_multi");
+        ridged.set_param("octaves", Number(8.0));
+        ridged.set_param("normalization", Text("unit_range".to_string()));
+        ridged.set_param("rotation", Boolean(true));
+        ridged.add_source(perlin);
+        let mut gradient = Node::new("gradient");
+        gradient.set_param("end", Numbers(vec![0.0, 2.5, -1.0]));
+        let mut add = Node::new("add");
+        add.add_source(ridged);
+        add.add_source(gradient);
+        let mut graph = Node::new("scale_bias");
+        graph.set_param("scale", Number(0.5));
+        graph.add_source(add);
+        graph
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph = graph();
+        assert_eq!(Node::from_json_str(graph.to_json_str().as_slice()), Ok(graph));
+    }
+
+    #[test]
+    fn ron_round_trip() {
+        let graph = graph();
+        assert_eq!(Node::from_ron_str(graph.to_ron_str().as_slice()), Ok(graph));
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let graph = graph();
+        assert_eq!(Node::from_toml_str(graph.to_toml_str().as_slice()), Ok(graph));
+    }
+
+    #[test]
+    fn formats_describe_the_same_graph() {
+        let json = Node::from_json_str(r#"{
+            "type": "scale_bias", "scale": 0.5,
+            "sources": [{ "type": "ridged_multi", "octaves": 8,
+                          "sources": [{ "type": "perlin", "seed": 3 }] }]
+        }"#);
+        let ron = Node::from_ron_str(r#"
+            // Comments and trailing commas are allowed.
+            scale_bias(
+                scale: 0.5,
+                sources: [ridged_multi(octaves: 8, sources: [perlin(seed: 3)])],
+            )
+        "#);
+        let toml = Node::from_toml_str(r#"
+            # Comments are allowed.
+            type = "scale_bias"
+            scale = 0.5
+
+            [[sources]]
+            type = "ridged_multi"
+            octaves = 8
+
+            [[sources.sources]]
+            type = "perlin"
+            seed = 3
+        "#);
+        assert!(json.is_ok());
+        assert_eq!(ron, json);
+        assert_eq!(toml, json);
+    }
+
+    #[test]
+    fn reads_hand_written_files() {
+        let mut perlin = Node::new("perlin");
+        perlin.set_param("seed", Number(1234.0));
+        let mut ridged = Node::new("ridged_multi");
+        ridged.set_param("octaves", Number(8.0));
+        ridged.set_param("normalization", Text("unit_range".to_string()));
+        ridged.add_source(perlin);
+        let mut terrace = Node::new("terrace");
+        terrace.set_param("points", Numbers(vec![-1.0, 0.0, 1.0]));
+        terrace.add_source(Node::new("const"));
+        let mut expected = Node::new("scale_bias");
+        expected.set_param("scale", Number(0.5));
+        expected.set_param("bias", Number(-1000.25));
+        expected.add_source(ridged);
+        expected.add_source(terrace);
+
+        let ron = Node::from_ron_str(r#"
+            scale_bias(bias: -1000.25, scale: 0.5, sources: [
+                ridged_multi(
+                    octaves: 8, normalization: "unit_range",
+                    sources: [perlin(seed: 1234,)],
+                ),
+                terrace(points: [-1, 0, 1.0], sources: [const()]), // The last source.
+            ])
+        "#);
+        let toml = Node::from_toml_str(r#"
+            "type" = "scale_bias"   # Quoted keys.
+            scale = +0.5
+            bias = -1_000.25
+            [[sources]]
+            type = "ridged_multi"
+            octaves = 8
+            normalization = "unit_range"
+                [[sources.sources]]
+                type = "perlin"
+                seed = 1_234
+            [[sources]]
+            type = "terrace"
+            points = [
+                -1.0, 0,
+                1e0,  # Trailing commas too.
+            ]
+                [[sources.sources]]
+                type = "const"
+        "#);
+        assert_eq!(ron, Ok(expected.clone()));
+        assert_eq!(toml, Ok(expected));
+    }
+
+    fn is_unsupported(result:Result<Node, GraphError>) -> bool {
+        match result {
+            Err(UnsupportedSyntax(_)) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn unsupported_syntax_is_reported() {
+        assert!(is_unsupported(Node::from_toml_str("type = 'perlin'")));
+        assert!(is_unsupported(Node::from_toml_str("type = \"perlin\"\noctaves.count = 3")));
+        assert!(is_unsupported(Node::from_toml_str("type = \"gradient\"\nend = { x = 1 }")));
+        assert!(is_unsupported(Node::from_toml_str("[perlin]\nseed = 3")));
+        assert!(is_unsupported(Node::from_ron_str("perlin(seed: 3) /* seeded */")));
+        assert!(is_unsupported(Node::from_ron_str("value(interpolation: 'q')")));
+        assert!(is_unsupported(Node::from_ron_str("(seed: 3)")));
+        // Malformed text is still a syntax error.
+        match Node::from_toml_str("type = ") {
+            Err(SyntaxError(_)) => {}
+            result => fail!("expected a syntax error, found {}", result),
+        }
+    }
+
+    /// Returns the name of the bad parameter reported when building `node`.
+    fn bad_parameter(node:&Node) -> String {
+        match node.build() {
+            Err(BadParameter(_, name, _)) => name,
+            _ => fail!("expected a bad parameter"),
+        }
+    }
+
+    #[test]
+    fn minkowski_order_requires_a_minkowski_distance() {
+        let mut worley = Node::new("worley");
+        worley.set_param("minkowski_order", Number(3.0));
+        assert_eq!(bad_parameter(&worley).as_slice(), "minkowski_order");
+        worley.set_param("distance", Text("minkowski".to_string()));
+        assert!(worley.build().is_ok());
+    }
+
+    #[test]
+    fn minkowski_order_must_be_positive() {
+        let mut worley = Node::new("worley");
+        worley.set_param("distance", Text("minkowski".to_string()));
+        worley.set_param("minkowski_order", Number(0.0));
+        assert_eq!(bad_parameter(&worley).as_slice(), "minkowski_order");
+        worley.set_param("minkowski_order", Number(-2.0));
+        assert_eq!(bad_parameter(&worley).as_slice(), "minkowski_order");
+    }
+
+    #[test]
+    fn fractal_sources_take_no_octave_parameters() {
+        let mut perlin = Node::new("perlin");
+        perlin.set_param("seed", Number(3.0));
+        let mut ridged = Node::new("ridged_multi");
+        ridged.add_source(perlin.clone());
+        assert!(ridged.build().is_ok());
+        let names = ["octaves", "frequency", "persistence", "lacunarity", "rotation", "normalization"];
+        for name in names.iter() {
+            let mut source = perlin.clone();
+            source.set_param(*name, Number(2.0));
+            let mut ridged = Node::new("ridged_multi");
+            ridged.add_source(source);
+            assert_eq!(bad_parameter(&ridged).as_slice(), *name);
+        }
+    }
+
+    #[test]
+    fn octave_counts_are_limited() {
+        let mut perlin = Node::new("perlin");
+        perlin.set_param("octaves", Number(1e9));
+        assert_eq!(bad_parameter(&perlin).as_slice(), "octaves");
+        perlin.set_param("octaves", Number(32.0));
+        assert!(perlin.build().is_ok());
+    }
+
+    #[test]
+    fn rotations_take_angles_or_a_quaternion() {
+        let mut rotate = Node::new("rotate_point");
+        rotate.add_source(Node::new("checkerboard"));
+        rotate.set_param("quaternion", Numbers(vec![0.5, 0.5, 0.5, 0.5]));
+        assert!(rotate.build().is_ok());
+        rotate.set_param("z", Number(1.0));
+        assert_eq!(bad_parameter(&rotate).as_slice(), "quaternion");
+        rotate.set_param("quaternion", Numbers(vec![1.0, 0.0]));
+        assert_eq!(bad_parameter(&rotate).as_slice(), "quaternion");
+    }
+}
\ No newline at end of file
@@ -1,8 +1,63 @@
 #![crate_id = "noise#0.0.0"]
 #![crate_type = "rlib"]
 #![crate_type = "dylib"]
+#![feature(macro_rules)]
 
+extern crate serialize;
+
+pub use combiners::add;
+pub use combiners::blend;
+pub use combiners::max;
+pub use combiners::min;
+pub use combiners::multiply;
+pub use combiners::power;
+pub use combiners::select;
+pub use fractals::billow;
+pub use fractals::fbm;
+pub use fractals::hetero_terrain;
+pub use fractals::hybrid_multi;
+pub use fractals::ridged_multi;
+pub use modifiers::abs;
+pub use modifiers::clamp;
+pub use modifiers::curve;
+pub use modifiers::exponent;
+pub use modifiers::invert;
+pub use modifiers::scale_bias;
+pub use modifiers::terrace;
+pub use noises::checkerboard;
+pub use noises::constant;
+pub use noises::curl;
+pub use noises::cylinders;
+pub use noises::gradient;
+pub use noises::open_simplex;
 pub use noises::perlin;
+pub use noises::perlin_vector;
+pub use noises::simplex;
+pub use noises::spheres;
+pub use noises::value;
+pub use noises::worley;
+pub use transformers::component_field;
+pub use transformers::displace;
+pub use transformers::domain_warp;
+pub use transformers::gradient_field;
+pub use transformers::rotate_point;
+pub use transformers::scale_point;
+pub use transformers::translate_point;
+pub use transformers::turbulence;
+
+// The macros of these modules are only visible to the modules declared after them.
+#[macro_escape]
+mod octaves;
+#[macro_escape]
+mod permutation;
 
+pub mod combiners;
+pub mod expression;
+pub mod fractals;
+pub mod graph;
+pub mod modifiers;
 pub mod noise;
-pub mod noises;
\ No newline at end of file
+pub mod noises;
+pub mod transformers;
+mod formats;
+mod math;
\ No newline at end of file
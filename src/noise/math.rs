@@ -0,0 +1,71 @@
+//! Interpolation helpers shared by the noise generators.
+
+/// Floating point types the noise generators can be evaluated with.
+pub trait NoiseFloat : Float {
+    /// Converts a constant to this type.
+    fn from_f64(v:f64) -> Self;
+    /// Converts a single precision parameter to this type.
+    fn from_f32(v:f32) -> Self;
+    /// Returns the floor as a signed integer.
+    fn floor_int(self) -> int;
+}
+
+impl NoiseFloat for f32 {
+    #[inline]
+    fn from_f64(v:f64) -> f32 { v as f32 }
+    #[inline]
+    fn from_f32(v:f32) -> f32 { v }
+    #[inline]
+    fn floor_int(self) -> int { self.floor() as int }
+}
+
+impl NoiseFloat for f64 {
+    #[inline]
+    fn from_f64(v:f64) -> f64 { v }
+    #[inline]
+    fn from_f32(v:f32) -> f64 { v as f64 }
+    #[inline]
+    fn floor_int(self) -> int { self.floor() as int }
+}
+
+/// Linear interpolation.
+#[inline]
+pub fn lerp<T: NoiseFloat>(t:T, a:T, b:T) -> T {
+    a + t * (b - a)
+}
+
+/// Compute cubic S-curve: 3t^2 - 2t^3.
+#[inline]
+pub fn cubic_curve<T: NoiseFloat>(t:T) -> T {
+    let two: T = NoiseFloat::from_f64(2.0);
+    let three: T = NoiseFloat::from_f64(3.0);
+    t * t * (three - two * t)
+}
+
+/// Compute quintic S-curve: 6t^5 - 15t^4 + 10t^3.
+#[inline]
+pub fn fade<T: NoiseFloat>(t:T) -> T {
+    let six: T = NoiseFloat::from_f64(6.0);
+    let fifteen: T = NoiseFloat::from_f64(15.0);
+    let ten: T = NoiseFloat::from_f64(10.0);
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+/// Compute the derivative of the quintic S-curve: 30t^4 - 60t^3 + 30t^2.
+#[inline]
+pub fn fade_derivative<T: NoiseFloat>(t:T) -> T {
+    let one: T = NoiseFloat::from_f64(1.0);
+    let two: T = NoiseFloat::from_f64(2.0);
+    let thirty: T = NoiseFloat::from_f64(30.0);
+    thirty * t * t * (t * (t - two) + one)
+}
+
+/// Cubic interpolation between n1 and n2, n0 and n3 being the
+/// neighbouring values.
+#[inline]
+pub fn cubic_interp<T: NoiseFloat>(n0:T, n1:T, n2:T, n3:T, t:T) -> T {
+    let p = (n3 - n2) - (n0 - n1);
+    let q = (n0 - n1) - p;
+    let r = n2 - n0;
+    t * (t * (t * p + q) + r) + n1
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use noise::Noise;
+
+/// Returns the absolute value of its source.
+pub struct Abs<S> {
+    /// The modified noise.
+    source: S,
+}
+
+impl<S: Noise> Abs<S> {
+    /// Creates the absolute value of `source`.
+    pub fn new(source:S) -> Abs<S> {
+        Abs { source: source }
+    }
+
+    /// Returns the absolute value of a value of the source.
+    fn modify(&self, value:f32) -> f32 {
+        value.abs()
+    }
+}
+
+impl_modifier_noise!(Abs)
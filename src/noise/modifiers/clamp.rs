@@ -0,0 +1,59 @@
+use noise::Noise;
+
+/// Clamps its source into a range, [-1, 1] by default.
+pub struct Clamp<S> {
+    /// The modified noise.
+    source: S,
+    /// The lower bound of the range.
+    lower: f32,
+    /// The upper bound of the range.
+    upper: f32,
+}
+
+impl<S: Noise> Clamp<S> {
+    /// Creates a clamp of `source` into [-1, 1].
+    pub fn new(source:S) -> Clamp<S> {
+        Clamp {
+            source: source,
+            lower: -1.0,
+            upper: 1.0,
+        }
+    }
+
+    /// Sets the bounds of the range, given in any order.
+    pub fn set_bounds(&mut self, a:f32, b:f32) {
+        self.lower = a.min(b);
+        self.upper = a.max(b);
+    }
+
+    /// Clamps a value of the source into the range.
+    fn modify(&self, value:f32) -> f32 {
+        value.max(self.lower).min(self.upper)
+    }
+}
+
+impl_modifier_noise!(Clamp)
+
+#[cfg(test)]
+mod test {
+    use noise::{Noise, Noise4D};
+    use noises::perlin::Perlin;
+    use super::Clamp;
+
+    #[test]
+    fn forwards_each_dimension_to_the_source() {
+        // The 1D, 2D and 4D values of Perlin are not slices of its 3D one.
+        let source = Perlin::with_seed(3);
+        let mut clamp = Clamp::new(Perlin::with_seed(3));
+        clamp.set_bounds(0.3, -0.2);
+        let modify = |v:f32| v.max(-0.2).min(0.3);
+        for i in range(0u, 50) {
+            let x = i as f32 * 0.37 - 9.0;
+            let y = i as f32 * -0.61 + 4.0;
+            assert_eq!(clamp.get_1d(x), modify(source.get_1d(x)));
+            assert_eq!(clamp.get_2d(x, y), modify(source.get_2d(x, y)));
+            assert_eq!(clamp.get_value(x, y, 0.5), modify(source.get_value(x, y, 0.5)));
+            assert_eq!(clamp.get_4d(x, y, 0.5, 1.5), modify(source.get_4d(x, y, 0.5, 1.5)));
+        }
+    }
+}
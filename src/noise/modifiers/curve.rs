@@ -0,0 +1,131 @@
+use math::cubic_interp;
+use noise::Noise;
+
+/// Remaps its source through a curve.
+///
+/// The curve is a cubic spline going through control points, each
+/// mapping an input value to an output value. Beyond the first and last
+/// points, the output is constant.
+pub struct Curve<S> {
+    /// The modified noise.
+    source: S,
+    /// The control points as (input, output), sorted by input.
+    control_points: Vec<(f32, f32)>,
+}
+
+impl<S: Noise> Curve<S> {
+    /// Creates a curve of `source` without any control point,
+    /// which leaves the source unchanged.
+    pub fn new(source:S) -> Curve<S> {
+        Curve {
+            source: source,
+            control_points: Vec::new(),
+        }
+    }
+
+    /// Adds a control point mapping `input` to `output`. If a point
+    /// already maps `input`, its output is replaced.
+    pub fn add_control_point(&mut self, input:f32, output:f32) {
+        let mut index = 0u;
+        while index < self.control_points.len() {
+            let (point_input, _) = *self.control_points.get(index);
+            if point_input == input {
+                *self.control_points.get_mut(index) = (input, output);
+                return;
+            }
+            if point_input > input {
+                break;
+            }
+            index += 1;
+        }
+        self.control_points.insert(index, (input, output));
+    }
+
+    /// Removes all the control points.
+    pub fn clear_control_points(&mut self) {
+        self.control_points.clear();
+    }
+
+    /// Maps a value of the source through the curve.
+    fn modify(&self, value:f32) -> f32 {
+        let count = self.control_points.len();
+        if count == 0 {
+            return value;
+        }
+
+        // Find the first control point above the value.
+        let mut index = 0u;
+        while index < count {
+            let (input, _) = *self.control_points.get(index);
+            if value < input {
+                break;
+            }
+            index += 1;
+        }
+
+        // The four control points around the value, repeated at the ends.
+        let last = count as int - 1;
+        let point = |i:int| -> (f32, f32) {
+            let i = if i < 0 { 0 } else if i > last { last } else { i };
+            *self.control_points.get(i as uint)
+        };
+        let index = index as int;
+        let (_, out0) = point(index - 2);
+        let (in1, out1) = point(index - 1);
+        let (in2, out2) = point(index);
+        let (_, out3) = point(index + 1);
+
+        // Beyond the ends, or on a single point.
+        if in1 == in2 {
+            return out1;
+        }
+        cubic_interp(out0, out1, out2, out3, (value - in1) / (in2 - in1))
+    }
+}
+
+impl_modifier_noise!(Curve)
+
+#[cfg(test)]
+mod test {
+    use noises::perlin::Perlin;
+    use super::Curve;
+
+    /// Returns `value` mapped through a curve going through (-1, -1),
+    /// (0, 0.5) and (1, 0).
+    fn curve(value:f32) -> f32 {
+        let mut curve = Curve::new(Perlin::new());
+        curve.add_control_point(1.0, 0.0);
+        curve.add_control_point(-1.0, -1.0);
+        curve.add_control_point(0.0, 0.5);
+        curve.modify(value)
+    }
+
+    #[test]
+    fn goes_through_the_control_points() {
+        assert_eq!(curve(-1.0), -1.0);
+        assert_eq!(curve(0.0), 0.5);
+        assert_eq!(curve(1.0), 0.0);
+    }
+
+    #[test]
+    fn constant_beyond_the_ends() {
+        assert_eq!(curve(-1.5), -1.0);
+        assert_eq!(curve(-100.0), -1.0);
+        assert_eq!(curve(1.5), 0.0);
+        assert_eq!(curve(100.0), 0.0);
+    }
+
+    #[test]
+    fn continuous_at_the_ends() {
+        let epsilon = 1e-4;
+        assert!((curve(-1.0 + epsilon) + 1.0).abs() < 1e-3);
+        assert!(curve(1.0 - epsilon).abs() < 1e-3);
+    }
+
+    #[test]
+    fn single_point_is_constant() {
+        let mut curve = Curve::new(Perlin::new());
+        curve.add_control_point(0.2, -0.4);
+        assert_eq!(curve.modify(0.7), -0.4);
+    }
+}
@@ -0,0 +1,35 @@
+use noise::Noise;
+
+/// Raises its source to a power.
+///
+/// The source is expected in [-1, 1]: it is mapped to [0, 1], raised,
+/// then mapped back, so the output stays in [-1, 1].
+pub struct Exponent<S> {
+    /// The modified noise.
+    source: S,
+    /// The power the source is raised to.
+    exponent: f32,
+}
+
+impl<S: Noise> Exponent<S> {
+    /// Creates an exponent of `source`, with an exponent of 1.
+    pub fn new(source:S) -> Exponent<S> {
+        Exponent {
+            source: source,
+            exponent: 1.0,
+        }
+    }
+
+    /// Sets the power the source is raised to.
+    pub fn set_exponent(&mut self, exponent:f32) {
+        self.exponent = exponent;
+    }
+
+    /// Raises a value of the source to the power.
+    fn modify(&self, value:f32) -> f32 {
+        let value = (value + 1.0) / 2.0;
+        value.abs().powf(self.exponent) * 2.0 - 1.0
+    }
+}
+
+impl_modifier_noise!(Exponent)
@@ -0,0 +1,21 @@
+use noise::Noise;
+
+/// Returns the opposite of its source.
+pub struct Invert<S> {
+    /// The modified noise.
+    source: S,
+}
+
+impl<S: Noise> Invert<S> {
+    /// Creates the opposite of `source`.
+    pub fn new(source:S) -> Invert<S> {
+        Invert { source: source }
+    }
+
+    /// Returns the opposite of a value of the source.
+    fn modify(&self, value:f32) -> f32 {
+        -value
+    }
+}
+
+impl_modifier_noise!(Invert)
@@ -0,0 +1,41 @@
+/// Implements the noise generator traits of a modifier keeping its source
+/// in its `source` field and mapping its values with its `modify` method,
+/// e.g. `impl_modifier_noise!(Abs)`. Every dimension is forwarded to the
+/// same dimension of the source, 4D where the source supports it.
+macro_rules! impl_modifier_noise(
+    ($name:ident) => (
+        /// Implements the noise generator common trait.
+        impl<S: ::noise::Noise> ::noise::Noise for $name<S> {
+            /// Returns the noise value at the point(x,y,z).
+            fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+                self.modify(self.source.get_value(x, y, z))
+            }
+
+            /// Returns the noise value at the point(x).
+            fn get_1d(&self, x:f32) -> f32 {
+                self.modify(self.source.get_1d(x))
+            }
+
+            /// Returns the noise value at the point(x,y).
+            fn get_2d(&self, x:f32, y:f32) -> f32 {
+                self.modify(self.source.get_2d(x, y))
+            }
+        }
+
+        /// Implements the 4D noise generator trait.
+        impl<S: ::noise::Noise4D> ::noise::Noise4D for $name<S> {
+            /// Returns the noise value at the point(x,y,z,w).
+            fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+                self.modify(self.source.get_4d(x, y, z, w))
+            }
+        }
+    )
+)
+
+pub mod abs;
+pub mod clamp;
+pub mod curve;
+pub mod exponent;
+pub mod invert;
+pub mod scale_bias;
+pub mod terrace;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use noise::Noise;
+
+/// Scales its source then adds a bias to it.
+pub struct ScaleBias<S> {
+    /// The modified noise.
+    source: S,
+    /// Multiplies the source.
+    scale: f32,
+    /// Added to the scaled source.
+    bias: f32,
+}
+
+impl<S: Noise> ScaleBias<S> {
+    /// Creates a scale and bias of `source` leaving it unchanged.
+    pub fn new(source:S) -> ScaleBias<S> {
+        ScaleBias {
+            source: source,
+            scale: 1.0,
+            bias: 0.0,
+        }
+    }
+
+    /// Sets the multiplier of the source.
+    pub fn set_scale(&mut self, scale:f32) {
+        self.scale = scale;
+    }
+
+    /// Sets the value added to the scaled source.
+    pub fn set_bias(&mut self, bias:f32) {
+        self.bias = bias;
+    }
+
+    /// Scales a value of the source and adds the bias to it.
+    fn modify(&self, value:f32) -> f32 {
+        value * self.scale + self.bias
+    }
+}
+
+impl_modifier_noise!(ScaleBias)
@@ -0,0 +1,146 @@
+use math::lerp;
+use noise::Noise;
+
+/// Maps its source onto terraces.
+///
+/// The output is flat around each control point and rises steeply
+/// towards the next one, like the steps of a terraced hillside.
+/// Inverted, the output rises steeply away from a point and flattens
+/// towards the next one.
+pub struct Terrace<S> {
+    /// The modified noise.
+    source: S,
+    /// The control points, sorted.
+    control_points: Vec<f32>,
+    /// Whether the terraces are inverted.
+    invert: bool,
+}
+
+impl<S: Noise> Terrace<S> {
+    /// Creates a terrace of `source` without any control point,
+    /// which leaves the source unchanged.
+    pub fn new(source:S) -> Terrace<S> {
+        Terrace {
+            source: source,
+            control_points: Vec::new(),
+            invert: false,
+        }
+    }
+
+    /// Adds a control point at `value`, if there is none there yet.
+    pub fn add_control_point(&mut self, value:f32) {
+        let mut index = 0u;
+        while index < self.control_points.len() {
+            let point = *self.control_points.get(index);
+            if point == value {
+                return;
+            }
+            if point > value {
+                break;
+            }
+            index += 1;
+        }
+        self.control_points.insert(index, value);
+    }
+
+    /// Replaces the control points by `count` points evenly
+    /// spread over [-1, 1].
+    pub fn make_control_points(&mut self, count:uint) {
+        self.control_points.clear();
+        if count == 1 {
+            self.control_points.push(0.0);
+            return;
+        }
+        for i in range(0, count) {
+            self.control_points.push(-1.0 + 2.0 * i as f32 / (count - 1) as f32);
+        }
+    }
+
+    /// Removes all the control points.
+    pub fn clear_control_points(&mut self) {
+        self.control_points.clear();
+    }
+
+    /// Sets whether the terraces are inverted.
+    pub fn set_invert(&mut self, invert:bool) {
+        self.invert = invert;
+    }
+
+    /// Maps a value of the source onto the terraces.
+    fn modify(&self, value:f32) -> f32 {
+        let count = self.control_points.len();
+        if count == 0 {
+            return value;
+        }
+
+        // Find the first control point above the value.
+        let mut index = 0u;
+        while index < count && value >= *self.control_points.get(index) {
+            index += 1;
+        }
+
+        // The two control points around the value, beyond the ends
+        // the output is flat.
+        if index == 0 {
+            return *self.control_points.get(0);
+        }
+        if index == count {
+            return *self.control_points.get(count - 1);
+        }
+        let mut v0 = *self.control_points.get(index - 1);
+        let mut v1 = *self.control_points.get(index);
+
+        let mut t = (value - v0) / (v1 - v0);
+        if self.invert {
+            t = 1.0 - t;
+            let tmp = v0;
+            v0 = v1;
+            v1 = tmp;
+        }
+        // Flatten the curve near the lower point.
+        t *= t;
+        lerp(t, v0, v1)
+    }
+}
+
+impl_modifier_noise!(Terrace)
+
+#[cfg(test)]
+mod test {
+    use noises::perlin::Perlin;
+    use super::Terrace;
+
+    /// Returns `value` mapped through terraces at -1, 0 and 1.
+    fn terrace(value:f32, invert:bool) -> f32 {
+        let mut terrace = Terrace::new(Perlin::new());
+        terrace.make_control_points(3);
+        terrace.set_invert(invert);
+        terrace.modify(value)
+    }
+
+    #[test]
+    fn flat_near_the_lower_point() {
+        assert_eq!(terrace(0.0, false), 0.0);
+        assert!((terrace(0.1, false) - 0.01).abs() < 1e-6);
+        assert!((terrace(0.5, false) - 0.25).abs() < 1e-6);
+        assert!((terrace(0.9, false) - 0.81).abs() < 1e-6);
+        assert_eq!(terrace(1.0, false), 1.0);
+    }
+
+    #[test]
+    fn inverted_terraces_are_flat_near_the_upper_point() {
+        assert_eq!(terrace(0.0, true), 0.0);
+        assert!((terrace(0.1, true) - 0.19).abs() < 1e-6);
+        assert!((terrace(0.5, true) - 0.75).abs() < 1e-6);
+        assert!((terrace(0.9, true) - 0.99).abs() < 1e-6);
+        assert_eq!(terrace(1.0, true), 1.0);
+        // Below zero, between the points -1 and 0.
+        assert!((terrace(-0.5, true) + 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn flat_beyond_the_ends() {
+        assert_eq!(terrace(-3.0, false), -1.0);
+        assert_eq!(terrace(3.0, true), 1.0);
+    }
+}
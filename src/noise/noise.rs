@@ -1,3 +1,187 @@
+use std::f32::{INFINITY, NEG_INFINITY};
+
+use permutation::SplitMix64;
+
+/// Common trait of the noise generators.
+///
+/// Only `get_value` has to be implemented, the lower dimension methods
+/// default to slices of the 3D noise. Generators override them when they
+/// have a cheaper dedicated path, which is then not always a slice: the
+/// octaves of the fractal generators are offset and rotated differently
+/// in each dimension.
 pub trait Noise {
+    /// Returns the noise value at the point(x,y,z).
     fn get_value(&self, x: f32, y:f32, z:f32) -> f32;
+
+    /// Returns the noise value at the point(x).
+    fn get_1d(&self, x:f32) -> f32 {
+        self.get_value(x, 0.0, 0.0)
+    }
+
+    /// Returns the noise value at the point(x,y).
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.get_value(x, y, 0.0)
+    }
+
+    /// Returns the noise value at the point(x,y,z), same as `get_value`.
+    fn get_3d(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.get_value(x, y, z)
+    }
+}
+
+/// Boxed generators, e.g. built from a graph, can be the sources
+/// of other generators.
+impl Noise for Box<Noise> {
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        (**self).get_value(x, y, z)
+    }
+
+    fn get_1d(&self, x:f32) -> f32 {
+        (**self).get_1d(x)
+    }
+
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        (**self).get_2d(x, y)
+    }
+}
+
+/// Single octave noise functions, used as the basis of the fractal
+/// generators.
+pub trait BasisNoise {
+    /// Returns the value of a single octave at the point(x,y,z), ignoring
+    /// the octave and frequency parameters of the generator. The lattice of
+    /// the noise has a unit spacing.
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32;
+
+    /// Returns the lowest and highest values of a single octave, which the
+    /// fractal generators scale their sum from. By default they are
+    /// measured by sampling the basis at `RANGE_SAMPLES` points.
+    fn get_range(&self) -> (f32, f32) {
+        measure_range(|x, y, z, _| self.get_basis(x, y, z))
+    }
+}
+
+impl BasisNoise for Box<BasisNoise> {
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        (**self).get_basis(x, y, z)
+    }
+
+    fn get_range(&self) -> (f32, f32) {
+        (**self).get_range()
+    }
+}
+
+/// Uses any generator as the basis of the fractal generators: its value,
+/// with its own octaves and frequency, is summed as a single octave.
+pub struct AsBasis<N> {
+    /// The generator sampled as a basis.
+    source: N,
+}
+
+impl<N: Noise> AsBasis<N> {
+    /// Wraps `source` into a basis noise.
+    pub fn new(source:N) -> AsBasis<N> {
+        AsBasis { source: source }
+    }
+}
+
+impl<N: Noise> BasisNoise for AsBasis<N> {
+    /// Returns the value of the generator at the point(x,y,z).
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.source.get_value(x, y, z)
+    }
+}
+
+/// The number of points sampled to measure the range of a basis noise.
+pub static RANGE_SAMPLES: uint = 8192;
+
+/// Returns the lowest and highest values of `basis` over pseudo random
+/// points(x,y,z,w) spread across a few hundred lattice cells along each
+/// axis. Bases of fewer dimensions ignore the extra coordinates.
+pub fn measure_range(basis: |f32, f32, f32, f32| -> f32) -> (f32, f32) {
+    let mut rng = SplitMix64::new(0);
+    let mut lower = INFINITY;
+    let mut upper = NEG_INFINITY;
+    for _ in range(0, RANGE_SAMPLES) {
+        let x = rng.next_f32() * 512.0 - 256.0;
+        let y = rng.next_f32() * 512.0 - 256.0;
+        let z = rng.next_f32() * 512.0 - 256.0;
+        let w = rng.next_f32() * 512.0 - 256.0;
+        let v = basis(x, y, z, w);
+        lower = lower.min(v);
+        upper = upper.max(v);
+    }
+    (lower, upper)
+}
+
+/// Noise generators also defined in 4D, to loop animations
+/// or tile seamlessly.
+pub trait Noise4D : Noise {
+    /// Returns the noise value at the point(x,y,z,w).
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32;
+}
+
+/// Noise generators that can also be evaluated in double precision,
+/// for coordinates far from the origin where single precision loses the
+/// position inside the lattice cells.
+pub trait Noise64 : Noise {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value_f64(&self, x:f64, y:f64, z:f64) -> f64;
+
+    /// Returns the noise value at the point(x).
+    fn get_1d_f64(&self, x:f64) -> f64 {
+        self.get_value_f64(x, 0.0, 0.0)
+    }
+
+    /// Returns the noise value at the point(x,y).
+    fn get_2d_f64(&self, x:f64, y:f64) -> f64 {
+        self.get_value_f64(x, y, 0.0)
+    }
+}
+
+/// 4D noise generators that can also be evaluated in double precision.
+pub trait Noise4D64 : Noise4D {
+    /// Returns the noise value at the point(x,y,z,w).
+    fn get_4d_f64(&self, x:f64, y:f64, z:f64, w:f64) -> f64;
+}
+
+/// Noise generators that can compute their derivatives analytically,
+/// e.g. for surface normals or slopes.
+pub trait NoiseWithDerivative : Noise {
+    /// Returns the noise value at the point(x,y,z) and its partial
+    /// derivatives along x, y and z.
+    fn get_value_with_derivative(&self, x:f32, y:f32, z:f32) -> (f32, [f32, ..3]);
+}
+
+/// Vector valued noise generators: displacements, flows or gradients.
+pub trait VectorNoise {
+    /// Returns the vector at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3];
+
+    /// Returns the vector at the point(x,y), the x and y components
+    /// of the 3D vector by default.
+    fn get_vector_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        let v = self.get_vector_3d(x, y, 0.0);
+        [v[0], v[1]]
+    }
+}
+
+/// How the fractal generators scale the sum of their octaves.
+///
+/// The bounded modes map the range the sum can reach to their output
+/// range. It is derived from the range of a single octave measured on the
+/// basis, see `BasisNoise::get_range`, and the other parameters of the
+/// generator. Sums rarely reach its ends, and the measure can miss the
+/// rarest extremes of the basis, so these modes also clamp.
+pub enum Normalization {
+    /// The sum is returned as is. For fBm, it is in the range of the basis
+    /// times the sum of the amplitudes of the octaves.
+    Raw,
+    /// The sum is divided by the sum of the amplitudes. For fBm, it is then
+    /// roughly in the range of the basis, which it can slightly exceed.
+    AmplitudeSum,
+    /// The range the sum can reach is mapped to [-1, 1]: always in [-1, 1].
+    Bounded,
+    /// The range the sum can reach is mapped to [0, 1]: always in [0, 1].
+    UnitRange,
 }
\ No newline at end of file
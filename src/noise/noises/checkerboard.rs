@@ -0,0 +1,52 @@
+use math::NoiseFloat;
+use noise::Noise;
+
+/// Checkerboard generator.
+///
+/// Space is divided in unit cubes, alternately returning 1 and -1.
+pub struct Checkerboard;
+
+impl Checkerboard {
+    /// Creates a checkerboard generator.
+    pub fn new() -> Checkerboard {
+        Checkerboard
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Checkerboard {
+    /// Returns 1 or -1 depending on the cube of the point(x,y,z).
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let parity = (x.floor_int() ^ y.floor_int() ^ z.floor_int()) & 1;
+        if parity == 0 { 1.0 } else { -1.0 }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::Noise;
+    use super::Checkerboard;
+
+    #[test]
+    fn alternates_across_the_origin() {
+        let board = Checkerboard::new();
+        assert_eq!(board.get_value(0.5, 0.5, 0.5), 1.0);
+        assert_eq!(board.get_value(-0.5, 0.5, 0.5), -1.0);
+        assert_eq!(board.get_value(0.5, -0.5, 0.5), -1.0);
+        assert_eq!(board.get_value(-0.5, -0.5, 0.5), 1.0);
+        assert_eq!(board.get_value(-0.5, -0.5, -0.5), -1.0);
+    }
+
+    #[test]
+    fn alternates_between_negative_cubes() {
+        let board = Checkerboard::new();
+        for i in range(-5i, 5) {
+            let x = i as f32 + 0.25;
+            for &(y, z) in [(-2.5f32, -7.75f32), (3.5, -0.1), (-0.9, 4.2)].iter() {
+                assert_eq!(board.get_value(x, y, z), -board.get_value(x + 1.0, y, z));
+                assert_eq!(board.get_value(x, y, z), -board.get_value(x, y - 1.0, z));
+                assert_eq!(board.get_value(x, y, z), -board.get_value(x, y, z - 1.0));
+            }
+        }
+    }
+}
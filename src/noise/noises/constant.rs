@@ -0,0 +1,27 @@
+use noise::Noise;
+
+/// Constant generator, returns the same value everywhere.
+pub struct Const {
+    /// The returned value.
+    value: f32,
+}
+
+impl Const {
+    /// Creates a constant generator returning `value`.
+    pub fn new(value:f32) -> Const {
+        Const { value: value }
+    }
+
+    /// Sets the returned value.
+    pub fn set_value(&mut self, value:f32) {
+        self.value = value;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Const {
+    /// Returns the constant value, whatever the point(x,y,z).
+    fn get_value(&self, _x:f32, _y:f32, _z:f32) -> f32 {
+        self.value
+    }
+}
@@ -0,0 +1,191 @@
+use noise::{AmplitudeSum, NoiseWithDerivative, VectorNoise};
+use noises::perlin::Perlin;
+
+/// Curl noise generator, in 2 and 3 dimensions.
+///
+/// Returns the curl of a potential field made of Perlin noise, computed
+/// from its analytic derivatives. The curl of a field has no divergence,
+/// so the result is a flow that neither converges nor diverges: suitable
+/// to advect particles, smoke or water.
+pub struct Curl {
+    /// The components of the potential field.
+    /// Only the z component is used in 2D.
+    x_potential: Perlin,
+    y_potential: Perlin,
+    z_potential: Perlin,
+    /// The seed of the x potential, the y and z ones follow it.
+    seed: u64,
+}
+
+impl Curl {
+    /// Creates a curl noise generator with default parameters.
+    pub fn new() -> Curl {
+        let mut curl = Curl {
+            x_potential: Perlin::new(),
+            y_potential: Perlin::new(),
+            z_potential: Perlin::new(),
+            seed: 0,
+        };
+        curl.set_seed(0);
+        // Clamping the potential would stop the flow where it saturates.
+        curl.x_potential.set_normalization(AmplitudeSum);
+        curl.y_potential.set_normalization(AmplitudeSum);
+        curl.z_potential.set_normalization(AmplitudeSum);
+        curl
+    }
+
+    /// Creates a curl noise generator with default parameters
+    /// and potentials seeded from `seed`.
+    pub fn with_seed(seed:u64) -> Curl {
+        let mut curl = Curl::new();
+        curl.set_seed(seed);
+        curl
+    }
+
+    /// Sets the seed of the potential field.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed;
+        self.x_potential.set_seed(seed);
+        self.y_potential.set_seed(seed + 1);
+        self.z_potential.set_seed(seed + 2);
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the number of octaves.
+    pub fn set_octave_count(&mut self, n:uint) {
+        self.x_potential.set_octave_count(n);
+        self.y_potential.set_octave_count(n);
+        self.z_potential.set_octave_count(n);
+    }
+
+    /// Sets the frequency of the first octave.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.x_potential.set_frequency(frequency);
+        self.y_potential.set_frequency(frequency);
+        self.z_potential.set_frequency(frequency);
+    }
+
+    /// Sets the persistence of the signal over succesive octaves.
+    pub fn set_persistence(&mut self, persistence:f32) {
+        self.x_potential.set_persistence(persistence);
+        self.y_potential.set_persistence(persistence);
+        self.z_potential.set_persistence(persistence);
+    }
+
+    /// Set the frequency multiplier.
+    pub fn set_lacuranity(&mut self, lacuranity:f32) {
+        self.x_potential.set_lacuranity(lacuranity);
+        self.y_potential.set_lacuranity(lacuranity);
+        self.z_potential.set_lacuranity(lacuranity);
+    }
+
+    /// Returns the flow vector at the point(x,y).
+    ///
+    /// The potential is a scalar field in 2D, its curl is
+    /// (dP/dy, -dP/dx).
+    pub fn get_curl_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        let (_, d) = self.z_potential.get_value_with_derivative(x, y, 0.0);
+        [d[1], -d[0]]
+    }
+
+    /// Returns the flow vector at the point(x,y,z).
+    pub fn get_curl_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        let (_, dx) = self.x_potential.get_value_with_derivative(x, y, z);
+        let (_, dy) = self.y_potential.get_value_with_derivative(x, y, z);
+        let (_, dz) = self.z_potential.get_value_with_derivative(x, y, z);
+        [dz[1] - dy[2],
+         dx[2] - dz[0],
+         dy[0] - dx[1]]
+    }
+}
+
+/// Implements the vector noise generator common trait.
+impl VectorNoise for Curl {
+    /// Returns the flow vector at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        self.get_curl_3d(x, y, z)
+    }
+
+    /// Returns the flow vector at the point(x,y).
+    fn get_vector_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        self.get_curl_2d(x, y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::{AmplitudeSum, Noise64};
+    use noises::perlin::Perlin;
+    use super::Curl;
+
+    /// Points on both sides of the origin, and far from it.
+    fn point(i:uint) -> [f32, ..3] {
+        [i as f32 * 0.731 - 14.2, 3.9 - i as f32 * 0.377, i as f32 * 0.113 + 0.05]
+    }
+
+    #[test]
+    fn flow_has_no_divergence() {
+        let mut curl = Curl::with_seed(11);
+        curl.set_octave_count(3);
+        let h = 1e-3f32;
+        for i in range(0u, 40) {
+            let p = point(i);
+            // The derivative of each component along its own axis.
+            let mut terms = [0.0f32, ..3];
+            for axis in range(0u, 3) {
+                let mut above = p;
+                let mut below = p;
+                above[axis] += h;
+                below[axis] -= h;
+                terms[axis] = (curl.get_curl_3d(above[0], above[1], above[2])[axis]
+                               - curl.get_curl_3d(below[0], below[1], below[2])[axis]) / (2.0 * h);
+            }
+            let divergence = terms[0] + terms[1] + terms[2];
+            let scale = terms[0].abs() + terms[1].abs() + terms[2].abs();
+            assert!(divergence.abs() < 0.01 * scale + 1e-3,
+                    "divergence {} at ({}, {}, {})", divergence, p[0], p[1], p[2]);
+        }
+    }
+
+    #[test]
+    fn flow_is_the_curl_of_the_potentials() {
+        let mut curl = Curl::with_seed(11);
+        curl.set_octave_count(3);
+        let mut potentials = [Perlin::with_seed(11), Perlin::with_seed(12), Perlin::with_seed(13)];
+        for potential in potentials.mut_iter() {
+            potential.set_octave_count(3);
+            potential.set_normalization(AmplitudeSum);
+        }
+
+        // The derivatives of the potentials, from finite differences
+        // in double precision.
+        let h = 1e-4f64;
+        let derivative = |potential:&Perlin, p:[f32, ..3], axis:uint| -> f64 {
+            let mut above = [p[0] as f64, p[1] as f64, p[2] as f64];
+            let mut below = above;
+            above[axis] += h;
+            below[axis] -= h;
+            (potential.get_value_f64(above[0], above[1], above[2])
+             - potential.get_value_f64(below[0], below[1], below[2])) / (2.0 * h)
+        };
+        let close = |a:f32, b:f64| (a as f64 - b).abs() < 1e-3 * (1.0 + b.abs());
+
+        let (px, py, pz) = (&potentials[0], &potentials[1], &potentials[2]);
+        for i in range(0u, 40) {
+            let p = point(i);
+            let flow = curl.get_curl_3d(p[0], p[1], p[2]);
+            assert!(close(flow[0], derivative(pz, p, 1) - derivative(py, p, 2)));
+            assert!(close(flow[1], derivative(px, p, 2) - derivative(pz, p, 0)));
+            assert!(close(flow[2], derivative(py, p, 0) - derivative(px, p, 1)));
+
+            let p = [p[0], p[1], 0.0];
+            let flow = curl.get_curl_2d(p[0], p[1]);
+            assert!(close(flow[0], derivative(pz, p, 1)));
+            assert!(close(flow[1], -derivative(pz, p, 0)));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use noise::Noise;
+
+/// Concentric cylinders generator.
+///
+/// The cylinders are centered on the y axis, one unit apart at a unit
+/// frequency. The output is 1 on their surface and falls to -1 halfway
+/// between two of them.
+pub struct Cylinders {
+    /// The number of cylinders per unit.
+    frequency: f32,
+}
+
+impl Cylinders {
+    /// Creates a concentric cylinders generator with a unit frequency.
+    pub fn new() -> Cylinders {
+        Cylinders { frequency: 1.0 }
+    }
+
+    /// Sets the number of cylinders per unit.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.frequency = frequency;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Cylinders {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value(&self, x:f32, _y:f32, z:f32) -> f32 {
+        let x = x * self.frequency;
+        let z = z * self.frequency;
+        let distance = (x * x + z * z).sqrt();
+        // The distance to the nearest cylinder, at most 0.5.
+        let from_smaller = distance - distance.floor();
+        let nearest = from_smaller.min(1.0 - from_smaller);
+        1.0 - nearest * 4.0
+    }
+}
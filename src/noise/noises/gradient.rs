@@ -0,0 +1,48 @@
+use noise::Noise;
+
+/// Linear gradient generator.
+///
+/// The output goes linearly from -1 at a start point to 1 at an end
+/// point, along the line joining them, and is constant beyond them.
+/// By default, it goes from the origin to (1,0,0).
+pub struct Gradient {
+    /// Where the output is -1.
+    start: [f32, ..3],
+    /// Where the output is 1.
+    end: [f32, ..3],
+}
+
+impl Gradient {
+    /// Creates a gradient from the origin to (1,0,0).
+    pub fn new() -> Gradient {
+        Gradient {
+            start: [0.0, 0.0, 0.0],
+            end: [1.0, 0.0, 0.0],
+        }
+    }
+
+    /// Sets the points where the output is -1 and 1.
+    pub fn set_points(&mut self, start:[f32, ..3], end:[f32, ..3]) {
+        self.start = start;
+        self.end = end;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Gradient {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let d = [self.end[0] - self.start[0],
+                 self.end[1] - self.start[1],
+                 self.end[2] - self.start[2]];
+        let length2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        if length2 == 0.0 {
+            return 0.0;
+        }
+        // Project the point onto the line, 0 at the start and 1 at the end.
+        let t = ((x - self.start[0]) * d[0]
+                 + (y - self.start[1]) * d[1]
+                 + (z - self.start[2]) * d[2]) / length2;
+        t.max(0.0).min(1.0) * 2.0 - 1.0
+    }
+}
@@ -1 +1,12 @@
-pub mod perlin;
\ No newline at end of file
+pub mod checkerboard;
+pub mod constant;
+pub mod curl;
+pub mod cylinders;
+pub mod gradient;
+pub mod open_simplex;
+pub mod perlin;
+pub mod perlin_vector;
+pub mod simplex;
+pub mod spheres;
+pub mod value;
+pub mod worley;
\ No newline at end of file
@@ -0,0 +1,1690 @@
+use noise::{BasisNoise, Noise, Noise4D};
+
+/// Primes used to hash the lattice coordinates.
+static PRIME_X: i64 = 0x5205402B9270C86F;
+static PRIME_Y: i64 = 0x598CD327003817B5;
+static PRIME_Z: i64 = 0x5BCC226E9FA0BACB;
+static PRIME_W: i64 = 0x56CC5227E58F554B;
+/// Multiplier mixing the hashed lattice coordinates.
+static HASH_MULTIPLIER: i64 = 0x53A3F72DEEC546F5;
+/// Seed modifier for the second lattice of the 3D noise.
+static SEED_FLIP_3D: i64 = -0x52D547B2E96ED629;
+/// Seed modifier between the lattice copies of the 4D noise.
+static SEED_OFFSET_4D: i64 = 0xE83DC3E0DA7164D;
+
+/// Skewing factor from 2D space to the triangular lattice.
+static SKEW_2D: f64 = 0.366025403784439;
+/// Unskewing factor from the triangular lattice to 2D space.
+static UNSKEW_2D: f64 = -0.21132486540518713;
+/// Scale used to rotate 3D coordinates so that the xy plane is favored.
+static ROOT3OVER3: f64 = 0.577350269189626;
+/// Rotation used for 3D coordinates when no plane is favored.
+static FALLBACK_ROTATE_3D: f64 = 2.0 / 3.0;
+
+/// Skewing factors from 4D space to the lattice of OpenSimplex2.
+static SKEW_4D: f64 = -0.138196601125011;
+static UNSKEW_4D: f64 = 0.309016994374947;
+/// Distance between the five lattice copies of the 4D OpenSimplex2 noise.
+static LATTICE_STEP_4D: f64 = 0.2;
+/// Skewing factors from 4D space to the lattice of OpenSimplex2S, the
+/// swapped factors of OpenSimplex2.
+static SKEW_4D_SMOOTH: f64 = 0.309016994374947;
+static UNSKEW_4D_SMOOTH: f64 = -0.138196601125011;
+
+// The gradient tables below are the unit gradients of the reference
+// implementation divided by the normalizer of each noise, which brings
+// its output into [-1, 1]. The normalizers are, for OpenSimplex2:
+// 2D 0.01001634121365712, 3D 0.07969837668935331, 4D 0.0220065933241897;
+// and for OpenSimplex2S: 2D 0.05481866495625118, 3D 0.2781926117527186,
+// 4D 0.11127945063342658.
+
+/// 24 gradients for 2D OpenSimplex2.
+static GRAD2: [[f64, ..2], ..24] = [
+    [38.20591014244875, 92.23722642870753],
+    [92.23722642870753, 38.20591014244875],
+    [92.23722642870753, -38.20591014244875],
+    [38.20591014244875, -92.23722642870753],
+    [-38.20591014244875, -92.23722642870753],
+    [-92.23722642870753, -38.20591014244875],
+    [-92.23722642870753, 38.20591014244875],
+    [-38.20591014244875, 92.23722642870753],
+    [13.031324456287654, 98.98273633310245],
+    [60.77682619065379, 79.20590197241988],
+    [79.20590197241988, 60.77682619065379],
+    [98.98273633310245, 13.031324456287555],
+    [98.98273633310245, -13.031324456287555],
+    [79.20590197241988, -60.776826190653686],
+    [60.77682619065379, -79.20590197241988],
+    [13.031324456287654, -98.98273633310245],
+    [-13.031324456287654, -98.98273633310245],
+    [-60.77682619065379, -79.20590197241988],
+    [-79.20590197241988, -60.77682619065379],
+    [-98.98273633310245, -13.031324456287654],
+    [-98.98273633310245, 13.031324456287555],
+    [-79.20590197241988, 60.77682619065379],
+    [-60.77682619065379, 79.20590197241988],
+    [-13.031324456287654, 98.98273633310245],
+];
+
+/// 24 gradients for 2D OpenSimplex2S.
+static GRAD2_SMOOTH: [[f64, ..2], ..24] = [
+    [6.980896610132626, 16.853375273706543],
+    [16.853375273706543, 6.980896610132626],
+    [16.853375273706543, -6.980896610132626],
+    [6.980896610132626, -16.853375273706543],
+    [-6.980896610132626, -16.853375273706543],
+    [-16.853375273706543, -6.980896610132626],
+    [-16.853375273706543, 6.980896610132626],
+    [-6.980896610132626, 16.853375273706543],
+    [2.3810538312857545, 18.085899431608684],
+    [11.105002821476075, 14.472321442420789],
+    [14.472321442420789, 11.105002821476075],
+    [18.085899431608684, 2.3810538312857363],
+    [18.085899431608684, -2.3810538312857363],
+    [14.472321442420789, -11.105002821476058],
+    [11.105002821476075, -14.472321442420789],
+    [2.3810538312857545, -18.085899431608684],
+    [-2.3810538312857545, -18.085899431608684],
+    [-11.105002821476075, -14.472321442420789],
+    [-14.472321442420789, -11.105002821476075],
+    [-18.085899431608684, -2.3810538312857545],
+    [-18.085899431608684, 2.3810538312857363],
+    [-14.472321442420789, 11.105002821476075],
+    [-11.105002821476075, 14.472321442420789],
+    [-2.3810538312857545, 18.085899431608684],
+];
+
+/// 48 gradients for 3D OpenSimplex2.
+static GRAD3: [[f64, ..3], ..48] = [
+    [27.914556905739307, 27.914556905739307, -12.54730700347611],
+    [27.914556905739307, 27.914556905739307, 12.54730700347611],
+    [38.724332878532614, 14.707342745703405, 0.0],
+    [14.707342745703405, 38.724332878532614, 0.0],
+    [-27.914556905739307, 27.914556905739307, -12.54730700347611],
+    [-27.914556905739307, 27.914556905739307, 12.54730700347611],
+    [-14.707342745703405, 38.724332878532614, 0.0],
+    [-38.724332878532614, 14.707342745703405, 0.0],
+    [-12.54730700347611, -27.914556905739307, -27.914556905739307],
+    [12.54730700347611, -27.914556905739307, -27.914556905739307],
+    [0.0, -38.724332878532614, -14.707342745703405],
+    [0.0, -14.707342745703405, -38.724332878532614],
+    [-12.54730700347611, -27.914556905739307, 27.914556905739307],
+    [12.54730700347611, -27.914556905739307, 27.914556905739307],
+    [0.0, -14.707342745703405, 38.724332878532614],
+    [0.0, -38.724332878532614, 14.707342745703405],
+    [-27.914556905739307, -27.914556905739307, -12.54730700347611],
+    [-27.914556905739307, -27.914556905739307, 12.54730700347611],
+    [-38.724332878532614, -14.707342745703405, 0.0],
+    [-14.707342745703405, -38.724332878532614, 0.0],
+    [-27.914556905739307, -12.54730700347611, -27.914556905739307],
+    [-27.914556905739307, 12.54730700347611, -27.914556905739307],
+    [-14.707342745703405, 0.0, -38.724332878532614],
+    [-38.724332878532614, 0.0, -14.707342745703405],
+    [-27.914556905739307, -12.54730700347611, 27.914556905739307],
+    [-27.914556905739307, 12.54730700347611, 27.914556905739307],
+    [-38.724332878532614, 0.0, 14.707342745703405],
+    [-14.707342745703405, 0.0, 38.724332878532614],
+    [-12.54730700347611, 27.914556905739307, -27.914556905739307],
+    [12.54730700347611, 27.914556905739307, -27.914556905739307],
+    [0.0, 14.707342745703405, -38.724332878532614],
+    [0.0, 38.724332878532614, -14.707342745703405],
+    [-12.54730700347611, 27.914556905739307, 27.914556905739307],
+    [12.54730700347611, 27.914556905739307, 27.914556905739307],
+    [0.0, 38.724332878532614, 14.707342745703405],
+    [0.0, 14.707342745703405, 38.724332878532614],
+    [27.914556905739307, -27.914556905739307, -12.54730700347611],
+    [27.914556905739307, -27.914556905739307, 12.54730700347611],
+    [14.707342745703405, -38.724332878532614, 0.0],
+    [38.724332878532614, -14.707342745703405, 0.0],
+    [27.914556905739307, -12.54730700347611, -27.914556905739307],
+    [27.914556905739307, 12.54730700347611, -27.914556905739307],
+    [38.724332878532614, 0.0, -14.707342745703405],
+    [14.707342745703405, 0.0, -38.724332878532614],
+    [27.914556905739307, -12.54730700347611, 27.914556905739307],
+    [27.914556905739307, 12.54730700347611, 27.914556905739307],
+    [14.707342745703405, 0.0, 38.724332878532614],
+    [38.724332878532614, 0.0, 14.707342745703405],
+];
+
+/// 48 gradients for 3D OpenSimplex2S.
+static GRAD3_SMOOTH: [[f64, ..3], ..48] = [
+    [7.997138591759381, 7.997138591759381, -3.5946317686139184],
+    [7.997138591759381, 7.997138591759381, 3.5946317686139184],
+    [11.093991495146318, 4.213452452462707, 0.0],
+    [4.213452452462707, 11.093991495146318, 0.0],
+    [-7.997138591759381, 7.997138591759381, -3.5946317686139184],
+    [-7.997138591759381, 7.997138591759381, 3.5946317686139184],
+    [-4.213452452462707, 11.093991495146318, 0.0],
+    [-11.093991495146318, 4.213452452462707, 0.0],
+    [-3.5946317686139184, -7.997138591759381, -7.997138591759381],
+    [3.5946317686139184, -7.997138591759381, -7.997138591759381],
+    [0.0, -11.093991495146318, -4.213452452462707],
+    [0.0, -4.213452452462707, -11.093991495146318],
+    [-3.5946317686139184, -7.997138591759381, 7.997138591759381],
+    [3.5946317686139184, -7.997138591759381, 7.997138591759381],
+    [0.0, -4.213452452462707, 11.093991495146318],
+    [0.0, -11.093991495146318, 4.213452452462707],
+    [-7.997138591759381, -7.997138591759381, -3.5946317686139184],
+    [-7.997138591759381, -7.997138591759381, 3.5946317686139184],
+    [-11.093991495146318, -4.213452452462707, 0.0],
+    [-4.213452452462707, -11.093991495146318, 0.0],
+    [-7.997138591759381, -3.5946317686139184, -7.997138591759381],
+    [-7.997138591759381, 3.5946317686139184, -7.997138591759381],
+    [-4.213452452462707, 0.0, -11.093991495146318],
+    [-11.093991495146318, 0.0, -4.213452452462707],
+    [-7.997138591759381, -3.5946317686139184, 7.997138591759381],
+    [-7.997138591759381, 3.5946317686139184, 7.997138591759381],
+    [-11.093991495146318, 0.0, 4.213452452462707],
+    [-4.213452452462707, 0.0, 11.093991495146318],
+    [-3.5946317686139184, 7.997138591759381, -7.997138591759381],
+    [3.5946317686139184, 7.997138591759381, -7.997138591759381],
+    [0.0, 4.213452452462707, -11.093991495146318],
+    [0.0, 11.093991495146318, -4.213452452462707],
+    [-3.5946317686139184, 7.997138591759381, 7.997138591759381],
+    [3.5946317686139184, 7.997138591759381, 7.997138591759381],
+    [0.0, 11.093991495146318, 4.213452452462707],
+    [0.0, 4.213452452462707, 11.093991495146318],
+    [7.997138591759381, -7.997138591759381, -3.5946317686139184],
+    [7.997138591759381, -7.997138591759381, 3.5946317686139184],
+    [4.213452452462707, -11.093991495146318, 0.0],
+    [11.093991495146318, -4.213452452462707, 0.0],
+    [7.997138591759381, -3.5946317686139184, -7.997138591759381],
+    [7.997138591759381, 3.5946317686139184, -7.997138591759381],
+    [11.093991495146318, 0.0, -4.213452452462707],
+    [4.213452452462707, 0.0, -11.093991495146318],
+    [7.997138591759381, -3.5946317686139184, 7.997138591759381],
+    [7.997138591759381, 3.5946317686139184, 7.997138591759381],
+    [4.213452452462707, 0.0, 11.093991495146318],
+    [11.093991495146318, 0.0, 4.213452452462707],
+];
+
+/// 160 gradients for 4D OpenSimplex2.
+static GRAD4: [[f64, ..4], ..160] = [
+    [-30.627455229084706, -14.72216859860944, -14.72216859860944, 26.331584326929192],
+    [-34.10288779456935, -18.19760116409408, 6.9508651309692855, 22.856151761444554],
+    [-34.10288779456935, 6.9508651309692855, -18.19760116409408, 22.856151761444554],
+    [-40.11598590168723, 3.710128671622361, 3.710128671622361, 20.68949997180156],
+    [-20.68949997180156, -3.710128671622361, -3.710128671622361, 40.11598590168723],
+    [-22.856151761444554, -6.9508651309692855, 18.19760116409408, 34.10288779456935],
+    [-22.856151761444554, 18.19760116409408, -6.9508651309692855, 34.10288779456935],
+    [-26.331584326929192, 14.72216859860944, 14.72216859860944, 30.627455229084706],
+    [-30.627455229084706, -14.72216859860944, 26.331584326929192, -14.72216859860944],
+    [-34.10288779456935, -18.19760116409408, 22.856151761444554, 6.9508651309692855],
+    [-34.10288779456935, 6.9508651309692855, 22.856151761444554, -18.19760116409408],
+    [-40.11598590168723, 3.710128671622361, 20.68949997180156, 3.710128671622361],
+    [-20.68949997180156, -3.710128671622361, 40.11598590168723, -3.710128671622361],
+    [-22.856151761444554, -6.9508651309692855, 34.10288779456935, 18.19760116409408],
+    [-22.856151761444554, 18.19760116409408, 34.10288779456935, -6.9508651309692855],
+    [-26.331584326929192, 14.72216859860944, 30.627455229084706, 14.72216859860944],
+    [-30.627455229084706, 26.331584326929192, -14.72216859860944, -14.72216859860944],
+    [-34.10288779456935, 22.856151761444554, -18.19760116409408, 6.9508651309692855],
+    [-34.10288779456935, 22.856151761444554, 6.9508651309692855, -18.19760116409408],
+    [-40.11598590168723, 20.68949997180156, 3.710128671622361, 3.710128671622361],
+    [-20.68949997180156, 40.11598590168723, -3.710128671622361, -3.710128671622361],
+    [-22.856151761444554, 34.10288779456935, -6.9508651309692855, 18.19760116409408],
+    [-22.856151761444554, 34.10288779456935, 18.19760116409408, -6.9508651309692855],
+    [-26.331584326929192, 30.627455229084706, 14.72216859860944, 14.72216859860944],
+    [26.331584326929192, -30.627455229084706, -14.72216859860944, -14.72216859860944],
+    [22.856151761444554, -34.10288779456935, -18.19760116409408, 6.9508651309692855],
+    [22.856151761444554, -34.10288779456935, 6.9508651309692855, -18.19760116409408],
+    [20.68949997180156, -40.11598590168723, 3.710128671622361, 3.710128671622361],
+    [40.11598590168723, -20.68949997180156, -3.710128671622361, -3.710128671622361],
+    [34.10288779456935, -22.856151761444554, -6.9508651309692855, 18.19760116409408],
+    [34.10288779456935, -22.856151761444554, 18.19760116409408, -6.9508651309692855],
+    [30.627455229084706, -26.331584326929192, 14.72216859860944, 14.72216859860944],
+    [-14.72216859860944, -30.627455229084706, -14.72216859860944, 26.331584326929192],
+    [-18.19760116409408, -34.10288779456935, 6.9508651309692855, 22.856151761444554],
+    [6.9508651309692855, -34.10288779456935, -18.19760116409408, 22.856151761444554],
+    [3.710128671622361, -40.11598590168723, 3.710128671622361, 20.68949997180156],
+    [-3.710128671622361, -20.68949997180156, -3.710128671622361, 40.11598590168723],
+    [-6.9508651309692855, -22.856151761444554, 18.19760116409408, 34.10288779456935],
+    [18.19760116409408, -22.856151761444554, -6.9508651309692855, 34.10288779456935],
+    [14.72216859860944, -26.331584326929192, 14.72216859860944, 30.627455229084706],
+    [-14.72216859860944, -30.627455229084706, 26.331584326929192, -14.72216859860944],
+    [-18.19760116409408, -34.10288779456935, 22.856151761444554, 6.9508651309692855],
+    [6.9508651309692855, -34.10288779456935, 22.856151761444554, -18.19760116409408],
+    [3.710128671622361, -40.11598590168723, 20.68949997180156, 3.710128671622361],
+    [-3.710128671622361, -20.68949997180156, 40.11598590168723, -3.710128671622361],
+    [-6.9508651309692855, -22.856151761444554, 34.10288779456935, 18.19760116409408],
+    [18.19760116409408, -22.856151761444554, 34.10288779456935, -6.9508651309692855],
+    [14.72216859860944, -26.331584326929192, 30.627455229084706, 14.72216859860944],
+    [-14.72216859860944, 26.331584326929192, -30.627455229084706, -14.72216859860944],
+    [-18.19760116409408, 22.856151761444554, -34.10288779456935, 6.9508651309692855],
+    [6.9508651309692855, 22.856151761444554, -34.10288779456935, -18.19760116409408],
+    [3.710128671622361, 20.68949997180156, -40.11598590168723, 3.710128671622361],
+    [-3.710128671622361, 40.11598590168723, -20.68949997180156, -3.710128671622361],
+    [-6.9508651309692855, 34.10288779456935, -22.856151761444554, 18.19760116409408],
+    [18.19760116409408, 34.10288779456935, -22.856151761444554, -6.9508651309692855],
+    [14.72216859860944, 30.627455229084706, -26.331584326929192, 14.72216859860944],
+    [26.331584326929192, -14.72216859860944, -30.627455229084706, -14.72216859860944],
+    [22.856151761444554, -18.19760116409408, -34.10288779456935, 6.9508651309692855],
+    [22.856151761444554, 6.9508651309692855, -34.10288779456935, -18.19760116409408],
+    [20.68949997180156, 3.710128671622361, -40.11598590168723, 3.710128671622361],
+    [40.11598590168723, -3.710128671622361, -20.68949997180156, -3.710128671622361],
+    [34.10288779456935, -6.9508651309692855, -22.856151761444554, 18.19760116409408],
+    [34.10288779456935, 18.19760116409408, -22.856151761444554, -6.9508651309692855],
+    [30.627455229084706, 14.72216859860944, -26.331584326929192, 14.72216859860944],
+    [-14.72216859860944, -14.72216859860944, -30.627455229084706, 26.331584326929192],
+    [-18.19760116409408, 6.9508651309692855, -34.10288779456935, 22.856151761444554],
+    [6.9508651309692855, -18.19760116409408, -34.10288779456935, 22.856151761444554],
+    [3.710128671622361, 3.710128671622361, -40.11598590168723, 20.68949997180156],
+    [-3.710128671622361, -3.710128671622361, -20.68949997180156, 40.11598590168723],
+    [-6.9508651309692855, 18.19760116409408, -22.856151761444554, 34.10288779456935],
+    [18.19760116409408, -6.9508651309692855, -22.856151761444554, 34.10288779456935],
+    [14.72216859860944, 14.72216859860944, -26.331584326929192, 30.627455229084706],
+    [-14.72216859860944, -14.72216859860944, 26.331584326929192, -30.627455229084706],
+    [-18.19760116409408, 6.9508651309692855, 22.856151761444554, -34.10288779456935],
+    [6.9508651309692855, -18.19760116409408, 22.856151761444554, -34.10288779456935],
+    [3.710128671622361, 3.710128671622361, 20.68949997180156, -40.11598590168723],
+    [-3.710128671622361, -3.710128671622361, 40.11598590168723, -20.68949997180156],
+    [-6.9508651309692855, 18.19760116409408, 34.10288779456935, -22.856151761444554],
+    [18.19760116409408, -6.9508651309692855, 34.10288779456935, -22.856151761444554],
+    [14.72216859860944, 14.72216859860944, 30.627455229084706, -26.331584326929192],
+    [-14.72216859860944, 26.331584326929192, -14.72216859860944, -30.627455229084706],
+    [-18.19760116409408, 22.856151761444554, 6.9508651309692855, -34.10288779456935],
+    [6.9508651309692855, 22.856151761444554, -18.19760116409408, -34.10288779456935],
+    [3.710128671622361, 20.68949997180156, 3.710128671622361, -40.11598590168723],
+    [-3.710128671622361, 40.11598590168723, -3.710128671622361, -20.68949997180156],
+    [-6.9508651309692855, 34.10288779456935, 18.19760116409408, -22.856151761444554],
+    [18.19760116409408, 34.10288779456935, -6.9508651309692855, -22.856151761444554],
+    [14.72216859860944, 30.627455229084706, 14.72216859860944, -26.331584326929192],
+    [26.331584326929192, -14.72216859860944, -14.72216859860944, -30.627455229084706],
+    [22.856151761444554, -18.19760116409408, 6.9508651309692855, -34.10288779456935],
+    [22.856151761444554, 6.9508651309692855, -18.19760116409408, -34.10288779456935],
+    [20.68949997180156, 3.710128671622361, 3.710128671622361, -40.11598590168723],
+    [40.11598590168723, -3.710128671622361, -3.710128671622361, -20.68949997180156],
+    [34.10288779456935, -6.9508651309692855, 18.19760116409408, -22.856151761444554],
+    [34.10288779456935, 18.19760116409408, -6.9508651309692855, -22.856151761444554],
+    [30.627455229084706, 14.72216859860944, 14.72216859860944, -26.331584326929192],
+    [-34.23251417237777, -17.253142872198573, -17.253142872198573, -17.253142872198573],
+    [-35.5424590983062, -19.63717246783094, -19.63717246783094, 5.511293827232425],
+    [-35.5424590983062, -19.63717246783094, 5.511293827232425, -19.63717246783094],
+    [-35.5424590983062, 5.511293827232425, -19.63717246783094, -19.63717246783094],
+    [-39.01789166379084, -23.11260503331558, 2.0358612617477827, 2.0358612617477827],
+    [-39.01789166379084, 2.0358612617477827, -23.11260503331558, 2.0358612617477827],
+    [-39.01789166379084, 2.0358612617477827, 2.0358612617477827, -23.11260503331558],
+    [-45.36290018724485, -1.536785613935258, -1.536785613935258, -1.536785613935258],
+    [-17.253142872198573, -34.23251417237777, -17.253142872198573, -17.253142872198573],
+    [-19.63717246783094, -35.5424590983062, -19.63717246783094, 5.511293827232425],
+    [-19.63717246783094, -35.5424590983062, 5.511293827232425, -19.63717246783094],
+    [5.511293827232425, -35.5424590983062, -19.63717246783094, -19.63717246783094],
+    [-23.11260503331558, -39.01789166379084, 2.0358612617477827, 2.0358612617477827],
+    [2.0358612617477827, -39.01789166379084, -23.11260503331558, 2.0358612617477827],
+    [2.0358612617477827, -39.01789166379084, 2.0358612617477827, -23.11260503331558],
+    [-1.536785613935258, -45.36290018724485, -1.536785613935258, -1.536785613935258],
+    [-17.253142872198573, -17.253142872198573, -34.23251417237777, -17.253142872198573],
+    [-19.63717246783094, -19.63717246783094, -35.5424590983062, 5.511293827232425],
+    [-19.63717246783094, 5.511293827232425, -35.5424590983062, -19.63717246783094],
+    [5.511293827232425, -19.63717246783094, -35.5424590983062, -19.63717246783094],
+    [-23.11260503331558, 2.0358612617477827, -39.01789166379084, 2.0358612617477827],
+    [2.0358612617477827, -23.11260503331558, -39.01789166379084, 2.0358612617477827],
+    [2.0358612617477827, 2.0358612617477827, -39.01789166379084, -23.11260503331558],
+    [-1.536785613935258, -1.536785613935258, -45.36290018724485, -1.536785613935258],
+    [-17.253142872198573, -17.253142872198573, -17.253142872198573, -34.23251417237777],
+    [-19.63717246783094, -19.63717246783094, 5.511293827232425, -35.5424590983062],
+    [-19.63717246783094, 5.511293827232425, -19.63717246783094, -35.5424590983062],
+    [5.511293827232425, -19.63717246783094, -19.63717246783094, -35.5424590983062],
+    [-23.11260503331558, 2.0358612617477827, 2.0358612617477827, -39.01789166379084],
+    [2.0358612617477827, -23.11260503331558, 2.0358612617477827, -39.01789166379084],
+    [2.0358612617477827, 2.0358612617477827, -23.11260503331558, -39.01789166379084],
+    [-1.536785613935258, -1.536785613935258, -1.536785613935258, -45.36290018724485],
+    [34.23251417237777, 17.253142872198573, 17.253142872198573, 17.253142872198573],
+    [35.5424590983062, 19.63717246783094, 19.63717246783094, -5.511293827232425],
+    [35.5424590983062, 19.63717246783094, -5.511293827232425, 19.63717246783094],
+    [35.5424590983062, -5.511293827232425, 19.63717246783094, 19.63717246783094],
+    [39.01789166379084, 23.11260503331558, -2.0358612617477827, -2.0358612617477827],
+    [39.01789166379084, -2.0358612617477827, 23.11260503331558, -2.0358612617477827],
+    [39.01789166379084, -2.0358612617477827, -2.0358612617477827, 23.11260503331558],
+    [45.36290018724485, 1.536785613935258, 1.536785613935258, 1.536785613935258],
+    [17.253142872198573, 34.23251417237777, 17.253142872198573, 17.253142872198573],
+    [19.63717246783094, 35.5424590983062, 19.63717246783094, -5.511293827232425],
+    [19.63717246783094, 35.5424590983062, -5.511293827232425, 19.63717246783094],
+    [-5.511293827232425, 35.5424590983062, 19.63717246783094, 19.63717246783094],
+    [23.11260503331558, 39.01789166379084, -2.0358612617477827, -2.0358612617477827],
+    [-2.0358612617477827, 39.01789166379084, 23.11260503331558, -2.0358612617477827],
+    [-2.0358612617477827, 39.01789166379084, -2.0358612617477827, 23.11260503331558],
+    [1.536785613935258, 45.36290018724485, 1.536785613935258, 1.536785613935258],
+    [17.253142872198573, 17.253142872198573, 34.23251417237777, 17.253142872198573],
+    [19.63717246783094, 19.63717246783094, 35.5424590983062, -5.511293827232425],
+    [19.63717246783094, -5.511293827232425, 35.5424590983062, 19.63717246783094],
+    [-5.511293827232425, 19.63717246783094, 35.5424590983062, 19.63717246783094],
+    [23.11260503331558, -2.0358612617477827, 39.01789166379084, -2.0358612617477827],
+    [-2.0358612617477827, 23.11260503331558, 39.01789166379084, -2.0358612617477827],
+    [-2.0358612617477827, -2.0358612617477827, 39.01789166379084, 23.11260503331558],
+    [1.536785613935258, 1.536785613935258, 45.36290018724485, 1.536785613935258],
+    [17.253142872198573, 17.253142872198573, 17.253142872198573, 34.23251417237777],
+    [19.63717246783094, 19.63717246783094, -5.511293827232425, 35.5424590983062],
+    [19.63717246783094, -5.511293827232425, 19.63717246783094, 35.5424590983062],
+    [-5.511293827232425, 19.63717246783094, 19.63717246783094, 35.5424590983062],
+    [23.11260503331558, -2.0358612617477827, -2.0358612617477827, 39.01789166379084],
+    [-2.0358612617477827, 23.11260503331558, -2.0358612617477827, 39.01789166379084],
+    [-2.0358612617477827, -2.0358612617477827, 23.11260503331558, 39.01789166379084],
+    [1.536785613935258, 1.536785613935258, 1.536785613935258, 45.36290018724485],
+];
+
+/// 160 gradients for 4D OpenSimplex2S.
+static GRAD4_SMOOTH: [[f64, ..4], ..160] = [
+    [-6.056877059912747, -2.9114519828734116, -2.9114519828734116, 5.20732682059337],
+    [-6.744177641097425, -3.5987525640580897, 1.3746011623693568, 4.5200262394086925],
+    [-6.744177641097425, 1.3746011623693568, -3.5987525640580897, 4.5200262394086925],
+    [-7.93332625666445, 0.733714018105369, 0.733714018105369, 4.091549781820227],
+    [-4.091549781820227, -0.733714018105369, -0.733714018105369, 7.93332625666445],
+    [-4.5200262394086925, -1.3746011623693568, 3.5987525640580897, 6.744177641097425],
+    [-4.5200262394086925, 3.5987525640580897, -1.3746011623693568, 6.744177641097425],
+    [-5.20732682059337, 2.9114519828734116, 2.9114519828734116, 6.056877059912747],
+    [-6.056877059912747, -2.9114519828734116, 5.20732682059337, -2.9114519828734116],
+    [-6.744177641097425, -3.5987525640580897, 4.5200262394086925, 1.3746011623693568],
+    [-6.744177641097425, 1.3746011623693568, 4.5200262394086925, -3.5987525640580897],
+    [-7.93332625666445, 0.733714018105369, 4.091549781820227, 0.733714018105369],
+    [-4.091549781820227, -0.733714018105369, 7.93332625666445, -0.733714018105369],
+    [-4.5200262394086925, -1.3746011623693568, 6.744177641097425, 3.5987525640580897],
+    [-4.5200262394086925, 3.5987525640580897, 6.744177641097425, -1.3746011623693568],
+    [-5.20732682059337, 2.9114519828734116, 6.056877059912747, 2.9114519828734116],
+    [-6.056877059912747, 5.20732682059337, -2.9114519828734116, -2.9114519828734116],
+    [-6.744177641097425, 4.5200262394086925, -3.5987525640580897, 1.3746011623693568],
+    [-6.744177641097425, 4.5200262394086925, 1.3746011623693568, -3.5987525640580897],
+    [-7.93332625666445, 4.091549781820227, 0.733714018105369, 0.733714018105369],
+    [-4.091549781820227, 7.93332625666445, -0.733714018105369, -0.733714018105369],
+    [-4.5200262394086925, 6.744177641097425, -1.3746011623693568, 3.5987525640580897],
+    [-4.5200262394086925, 6.744177641097425, 3.5987525640580897, -1.3746011623693568],
+    [-5.20732682059337, 6.056877059912747, 2.9114519828734116, 2.9114519828734116],
+    [5.20732682059337, -6.056877059912747, -2.9114519828734116, -2.9114519828734116],
+    [4.5200262394086925, -6.744177641097425, -3.5987525640580897, 1.3746011623693568],
+    [4.5200262394086925, -6.744177641097425, 1.3746011623693568, -3.5987525640580897],
+    [4.091549781820227, -7.93332625666445, 0.733714018105369, 0.733714018105369],
+    [7.93332625666445, -4.091549781820227, -0.733714018105369, -0.733714018105369],
+    [6.744177641097425, -4.5200262394086925, -1.3746011623693568, 3.5987525640580897],
+    [6.744177641097425, -4.5200262394086925, 3.5987525640580897, -1.3746011623693568],
+    [6.056877059912747, -5.20732682059337, 2.9114519828734116, 2.9114519828734116],
+    [-2.9114519828734116, -6.056877059912747, -2.9114519828734116, 5.20732682059337],
+    [-3.5987525640580897, -6.744177641097425, 1.3746011623693568, 4.5200262394086925],
+    [1.3746011623693568, -6.744177641097425, -3.5987525640580897, 4.5200262394086925],
+    [0.733714018105369, -7.93332625666445, 0.733714018105369, 4.091549781820227],
+    [-0.733714018105369, -4.091549781820227, -0.733714018105369, 7.93332625666445],
+    [-1.3746011623693568, -4.5200262394086925, 3.5987525640580897, 6.744177641097425],
+    [3.5987525640580897, -4.5200262394086925, -1.3746011623693568, 6.744177641097425],
+    [2.9114519828734116, -5.20732682059337, 2.9114519828734116, 6.056877059912747],
+    [-2.9114519828734116, -6.056877059912747, 5.20732682059337, -2.9114519828734116],
+    [-3.5987525640580897, -6.744177641097425, 4.5200262394086925, 1.3746011623693568],
+    [1.3746011623693568, -6.744177641097425, 4.5200262394086925, -3.5987525640580897],
+    [0.733714018105369, -7.93332625666445, 4.091549781820227, 0.733714018105369],
+    [-0.733714018105369, -4.091549781820227, 7.93332625666445, -0.733714018105369],
+    [-1.3746011623693568, -4.5200262394086925, 6.744177641097425, 3.5987525640580897],
+    [3.5987525640580897, -4.5200262394086925, 6.744177641097425, -1.3746011623693568],
+    [2.9114519828734116, -5.20732682059337, 6.056877059912747, 2.9114519828734116],
+    [-2.9114519828734116, 5.20732682059337, -6.056877059912747, -2.9114519828734116],
+    [-3.5987525640580897, 4.5200262394086925, -6.744177641097425, 1.3746011623693568],
+    [1.3746011623693568, 4.5200262394086925, -6.744177641097425, -3.5987525640580897],
+    [0.733714018105369, 4.091549781820227, -7.93332625666445, 0.733714018105369],
+    [-0.733714018105369, 7.93332625666445, -4.091549781820227, -0.733714018105369],
+    [-1.3746011623693568, 6.744177641097425, -4.5200262394086925, 3.5987525640580897],
+    [3.5987525640580897, 6.744177641097425, -4.5200262394086925, -1.3746011623693568],
+    [2.9114519828734116, 6.056877059912747, -5.20732682059337, 2.9114519828734116],
+    [5.20732682059337, -2.9114519828734116, -6.056877059912747, -2.9114519828734116],
+    [4.5200262394086925, -3.5987525640580897, -6.744177641097425, 1.3746011623693568],
+    [4.5200262394086925, 1.3746011623693568, -6.744177641097425, -3.5987525640580897],
+    [4.091549781820227, 0.733714018105369, -7.93332625666445, 0.733714018105369],
+    [7.93332625666445, -0.733714018105369, -4.091549781820227, -0.733714018105369],
+    [6.744177641097425, -1.3746011623693568, -4.5200262394086925, 3.5987525640580897],
+    [6.744177641097425, 3.5987525640580897, -4.5200262394086925, -1.3746011623693568],
+    [6.056877059912747, 2.9114519828734116, -5.20732682059337, 2.9114519828734116],
+    [-2.9114519828734116, -2.9114519828734116, -6.056877059912747, 5.20732682059337],
+    [-3.5987525640580897, 1.3746011623693568, -6.744177641097425, 4.5200262394086925],
+    [1.3746011623693568, -3.5987525640580897, -6.744177641097425, 4.5200262394086925],
+    [0.733714018105369, 0.733714018105369, -7.93332625666445, 4.091549781820227],
+    [-0.733714018105369, -0.733714018105369, -4.091549781820227, 7.93332625666445],
+    [-1.3746011623693568, 3.5987525640580897, -4.5200262394086925, 6.744177641097425],
+    [3.5987525640580897, -1.3746011623693568, -4.5200262394086925, 6.744177641097425],
+    [2.9114519828734116, 2.9114519828734116, -5.20732682059337, 6.056877059912747],
+    [-2.9114519828734116, -2.9114519828734116, 5.20732682059337, -6.056877059912747],
+    [-3.5987525640580897, 1.3746011623693568, 4.5200262394086925, -6.744177641097425],
+    [1.3746011623693568, -3.5987525640580897, 4.5200262394086925, -6.744177641097425],
+    [0.733714018105369, 0.733714018105369, 4.091549781820227, -7.93332625666445],
+    [-0.733714018105369, -0.733714018105369, 7.93332625666445, -4.091549781820227],
+    [-1.3746011623693568, 3.5987525640580897, 6.744177641097425, -4.5200262394086925],
+    [3.5987525640580897, -1.3746011623693568, 6.744177641097425, -4.5200262394086925],
+    [2.9114519828734116, 2.9114519828734116, 6.056877059912747, -5.20732682059337],
+    [-2.9114519828734116, 5.20732682059337, -2.9114519828734116, -6.056877059912747],
+    [-3.5987525640580897, 4.5200262394086925, 1.3746011623693568, -6.744177641097425],
+    [1.3746011623693568, 4.5200262394086925, -3.5987525640580897, -6.744177641097425],
+    [0.733714018105369, 4.091549781820227, 0.733714018105369, -7.93332625666445],
+    [-0.733714018105369, 7.93332625666445, -0.733714018105369, -4.091549781820227],
+    [-1.3746011623693568, 6.744177641097425, 3.5987525640580897, -4.5200262394086925],
+    [3.5987525640580897, 6.744177641097425, -1.3746011623693568, -4.5200262394086925],
+    [2.9114519828734116, 6.056877059912747, 2.9114519828734116, -5.20732682059337],
+    [5.20732682059337, -2.9114519828734116, -2.9114519828734116, -6.056877059912747],
+    [4.5200262394086925, -3.5987525640580897, 1.3746011623693568, -6.744177641097425],
+    [4.5200262394086925, 1.3746011623693568, -3.5987525640580897, -6.744177641097425],
+    [4.091549781820227, 0.733714018105369, 0.733714018105369, -7.93332625666445],
+    [7.93332625666445, -0.733714018105369, -0.733714018105369, -4.091549781820227],
+    [6.744177641097425, -1.3746011623693568, 3.5987525640580897, -4.5200262394086925],
+    [6.744177641097425, 3.5987525640580897, -1.3746011623693568, -4.5200262394086925],
+    [6.056877059912747, 2.9114519828734116, 2.9114519828734116, -5.20732682059337],
+    [-6.769812517656215, -3.4119767539413566, -3.4119767539413566, -3.4119767539413566],
+    [-7.028866863251029, -3.883441786211694, -3.883441786211694, 1.0899119402157522],
+    [-7.028866863251029, -3.883441786211694, 1.0899119402157522, -3.883441786211694],
+    [-7.028866863251029, 1.0899119402157522, -3.883441786211694, -3.883441786211694],
+    [-7.7161674444357065, -4.570742367396373, 0.4026113590310739, 0.4026113590310739],
+    [-7.7161674444357065, 0.4026113590310739, -4.570742367396373, 0.4026113590310739],
+    [-7.7161674444357065, 0.4026113590310739, 0.4026113590310739, -4.570742367396373],
+    [-8.970954571972321, -0.3039142972025026, -0.3039142972025026, -0.3039142972025026],
+    [-3.4119767539413566, -6.769812517656215, -3.4119767539413566, -3.4119767539413566],
+    [-3.883441786211694, -7.028866863251029, -3.883441786211694, 1.0899119402157522],
+    [-3.883441786211694, -7.028866863251029, 1.0899119402157522, -3.883441786211694],
+    [1.0899119402157522, -7.028866863251029, -3.883441786211694, -3.883441786211694],
+    [-4.570742367396373, -7.7161674444357065, 0.4026113590310739, 0.4026113590310739],
+    [0.4026113590310739, -7.7161674444357065, -4.570742367396373, 0.4026113590310739],
+    [0.4026113590310739, -7.7161674444357065, 0.4026113590310739, -4.570742367396373],
+    [-0.3039142972025026, -8.970954571972321, -0.3039142972025026, -0.3039142972025026],
+    [-3.4119767539413566, -3.4119767539413566, -6.769812517656215, -3.4119767539413566],
+    [-3.883441786211694, -3.883441786211694, -7.028866863251029, 1.0899119402157522],
+    [-3.883441786211694, 1.0899119402157522, -7.028866863251029, -3.883441786211694],
+    [1.0899119402157522, -3.883441786211694, -7.028866863251029, -3.883441786211694],
+    [-4.570742367396373, 0.4026113590310739, -7.7161674444357065, 0.4026113590310739],
+    [0.4026113590310739, -4.570742367396373, -7.7161674444357065, 0.4026113590310739],
+    [0.4026113590310739, 0.4026113590310739, -7.7161674444357065, -4.570742367396373],
+    [-0.3039142972025026, -0.3039142972025026, -8.970954571972321, -0.3039142972025026],
+    [-3.4119767539413566, -3.4119767539413566, -3.4119767539413566, -6.769812517656215],
+    [-3.883441786211694, -3.883441786211694, 1.0899119402157522, -7.028866863251029],
+    [-3.883441786211694, 1.0899119402157522, -3.883441786211694, -7.028866863251029],
+    [1.0899119402157522, -3.883441786211694, -3.883441786211694, -7.028866863251029],
+    [-4.570742367396373, 0.4026113590310739, 0.4026113590310739, -7.7161674444357065],
+    [0.4026113590310739, -4.570742367396373, 0.4026113590310739, -7.7161674444357065],
+    [0.4026113590310739, 0.4026113590310739, -4.570742367396373, -7.7161674444357065],
+    [-0.3039142972025026, -0.3039142972025026, -0.3039142972025026, -8.970954571972321],
+    [6.769812517656215, 3.4119767539413566, 3.4119767539413566, 3.4119767539413566],
+    [7.028866863251029, 3.883441786211694, 3.883441786211694, -1.0899119402157522],
+    [7.028866863251029, 3.883441786211694, -1.0899119402157522, 3.883441786211694],
+    [7.028866863251029, -1.0899119402157522, 3.883441786211694, 3.883441786211694],
+    [7.7161674444357065, 4.570742367396373, -0.4026113590310739, -0.4026113590310739],
+    [7.7161674444357065, -0.4026113590310739, 4.570742367396373, -0.4026113590310739],
+    [7.7161674444357065, -0.4026113590310739, -0.4026113590310739, 4.570742367396373],
+    [8.970954571972321, 0.3039142972025026, 0.3039142972025026, 0.3039142972025026],
+    [3.4119767539413566, 6.769812517656215, 3.4119767539413566, 3.4119767539413566],
+    [3.883441786211694, 7.028866863251029, 3.883441786211694, -1.0899119402157522],
+    [3.883441786211694, 7.028866863251029, -1.0899119402157522, 3.883441786211694],
+    [-1.0899119402157522, 7.028866863251029, 3.883441786211694, 3.883441786211694],
+    [4.570742367396373, 7.7161674444357065, -0.4026113590310739, -0.4026113590310739],
+    [-0.4026113590310739, 7.7161674444357065, 4.570742367396373, -0.4026113590310739],
+    [-0.4026113590310739, 7.7161674444357065, -0.4026113590310739, 4.570742367396373],
+    [0.3039142972025026, 8.970954571972321, 0.3039142972025026, 0.3039142972025026],
+    [3.4119767539413566, 3.4119767539413566, 6.769812517656215, 3.4119767539413566],
+    [3.883441786211694, 3.883441786211694, 7.028866863251029, -1.0899119402157522],
+    [3.883441786211694, -1.0899119402157522, 7.028866863251029, 3.883441786211694],
+    [-1.0899119402157522, 3.883441786211694, 7.028866863251029, 3.883441786211694],
+    [4.570742367396373, -0.4026113590310739, 7.7161674444357065, -0.4026113590310739],
+    [-0.4026113590310739, 4.570742367396373, 7.7161674444357065, -0.4026113590310739],
+    [-0.4026113590310739, -0.4026113590310739, 7.7161674444357065, 4.570742367396373],
+    [0.3039142972025026, 0.3039142972025026, 8.970954571972321, 0.3039142972025026],
+    [3.4119767539413566, 3.4119767539413566, 3.4119767539413566, 6.769812517656215],
+    [3.883441786211694, 3.883441786211694, -1.0899119402157522, 7.028866863251029],
+    [3.883441786211694, -1.0899119402157522, 3.883441786211694, 7.028866863251029],
+    [-1.0899119402157522, 3.883441786211694, 3.883441786211694, 7.028866863251029],
+    [4.570742367396373, -0.4026113590310739, -0.4026113590310739, 7.7161674444357065],
+    [-0.4026113590310739, 4.570742367396373, -0.4026113590310739, 7.7161674444357065],
+    [-0.4026113590310739, -0.4026113590310739, 4.570742367396373, 7.7161674444357065],
+    [0.3039142972025026, 0.3039142972025026, 0.3039142972025026, 8.970954571972321],
+];
+
+/// Start of the vertices of each sub-cube in `LOOKUP_4D_VERTICES`, the
+/// vertices of sub-cube `i` are in `[LOOKUP_4D_START[i], LOOKUP_4D_START[i + 1])`.
+static LOOKUP_4D_START: [u16, ..257] = [
+    0, 21, 40, 60, 78, 97, 113, 127, 146, 166, 180, 190,
+    208, 226, 245, 263, 280, 299, 315, 329, 348, 364, 378, 392,
+    405, 419, 433, 444, 456, 475, 488, 500, 518, 538, 552, 562,
+    580, 594, 608, 619, 631, 641, 652, 662, 675, 693, 705, 718,
+    737, 755, 774, 792, 809, 828, 841, 853, 871, 889, 901, 914,
+    933, 950, 968, 987, 1005, 1024, 1040, 1054, 1073, 1089, 1103, 1117,
+    1130, 1144, 1158, 1169, 1181, 1200, 1213, 1225, 1243, 1259, 1273, 1287,
+    1300, 1314, 1334, 1354, 1364, 1378, 1398, 1417, 1428, 1441, 1451, 1462,
+    1472, 1486, 1500, 1511, 1523, 1537, 1557, 1576, 1587, 1598, 1617, 1637,
+    1651, 1663, 1674, 1688, 1702, 1721, 1734, 1746, 1764, 1777, 1787, 1798,
+    1808, 1820, 1831, 1845, 1859, 1877, 1887, 1901, 1921, 1941, 1955, 1965,
+    1983, 1997, 2011, 2022, 2034, 2044, 2055, 2065, 2078, 2096, 2108, 2121,
+    2140, 2154, 2168, 2179, 2191, 2205, 2225, 2244, 2255, 2266, 2285, 2305,
+    2319, 2331, 2342, 2356, 2370, 2380, 2391, 2401, 2414, 2425, 2444, 2464,
+    2478, 2488, 2508, 2528, 2542, 2555, 2569, 2583, 2599, 2617, 2629, 2642,
+    2661, 2673, 2684, 2698, 2712, 2725, 2739, 2753, 2769, 2788, 2802, 2818,
+    2837, 2855, 2874, 2892, 2909, 2928, 2941, 2953, 2971, 2989, 3001, 3014,
+    3033, 3050, 3068, 3087, 3105, 3124, 3137, 3149, 3167, 3180, 3190, 3201,
+    3211, 3223, 3234, 3248, 3262, 3280, 3290, 3304, 3324, 3342, 3354, 3367,
+    3386, 3398, 3409, 3423, 3437, 3450, 3464, 3478, 3494, 3513, 3527, 3543,
+    3562, 3579, 3597, 3616, 3634, 3652, 3662, 3676, 3696, 3715, 3729, 3745,
+    3764, 3782, 3802, 3821, 3842,
+];
+
+/// Vertices that can contribute to the 4D OpenSimplex2S noise, for each of
+/// the 256 sub-cubes of the skewed unit hypercube. A vertex is packed with
+/// two bits per axis, x in the lowest bits, each storing the lattice
+/// offset plus one.
+static LOOKUP_4D_VERTICES: [u8, ..3842] = [
+    0, 21, 69, 81, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170,
+    1, 5, 17, 21, 65, 69, 81, 85, 86, 89, 90, 101, 102, 106, 149, 150, 154, 166, 170,
+    1, 5, 17, 21, 65, 69, 81, 85, 86, 89, 90, 101, 102, 106, 149, 150, 154, 166, 170, 171,
+    1, 21, 22, 69, 70, 81, 82, 85, 86, 87, 90, 102, 106, 150, 154, 166, 170, 171,
+    4, 5, 20, 21, 68, 69, 84, 85, 86, 89, 90, 101, 105, 106, 149, 153, 154, 169, 170,
+    5, 21, 69, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 170,
+    5, 21, 69, 85, 86, 89, 90, 102, 106, 150, 154, 166, 170, 171,
+    5, 21, 22, 69, 70, 85, 86, 89, 90, 91, 102, 106, 107, 150, 154, 155, 166, 170, 171,
+    4, 5, 20, 21, 68, 69, 84, 85, 86, 89, 90, 101, 105, 106, 149, 153, 154, 169, 170, 174,
+    5, 21, 69, 85, 86, 89, 90, 105, 106, 153, 154, 169, 170, 174,
+    5, 21, 69, 85, 86, 89, 90, 106, 154, 170,
+    5, 21, 22, 69, 70, 85, 86, 89, 90, 91, 102, 106, 107, 150, 154, 155, 170, 171,
+    4, 21, 25, 69, 73, 84, 85, 88, 89, 90, 93, 105, 106, 153, 154, 169, 170, 174,
+    5, 21, 25, 69, 73, 85, 86, 89, 90, 94, 105, 106, 110, 153, 154, 158, 169, 170, 174,
+    5, 21, 25, 69, 73, 85, 86, 89, 90, 94, 105, 106, 110, 153, 154, 158, 170, 174,
+    5, 21, 26, 69, 74, 85, 86, 89, 90, 91, 94, 106, 154, 170, 171, 174, 175,
+    16, 17, 20, 21, 80, 81, 84, 85, 86, 89, 101, 102, 105, 106, 149, 165, 166, 169, 170,
+    17, 21, 81, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 165, 166, 170,
+    17, 21, 81, 85, 86, 90, 101, 102, 106, 150, 154, 166, 170, 171,
+    17, 21, 22, 81, 82, 85, 86, 90, 101, 102, 103, 106, 107, 150, 154, 166, 167, 170, 171,
+    20, 21, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 153, 165, 169, 170,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 149, 154, 166, 169, 170,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 150, 154, 166, 170, 171,
+    21, 22, 85, 86, 90, 102, 106, 107, 150, 154, 166, 170, 171,
+    20, 21, 84, 85, 89, 90, 101, 105, 106, 153, 154, 169, 170, 174,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 153, 154, 169, 170, 174,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 154, 170,
+    21, 22, 85, 86, 89, 90, 102, 106, 107, 154, 170, 171,
+    20, 21, 25, 84, 85, 88, 89, 90, 101, 105, 106, 109, 110, 153, 154, 169, 170, 173, 174,
+    21, 25, 85, 89, 90, 105, 106, 110, 153, 154, 169, 170, 174,
+    21, 25, 85, 86, 89, 90, 105, 106, 110, 154, 170, 174,
+    21, 22, 25, 26, 85, 86, 89, 90, 102, 105, 106, 107, 110, 154, 170, 171, 174, 175,
+    16, 17, 20, 21, 80, 81, 84, 85, 86, 89, 101, 102, 105, 106, 149, 165, 166, 169, 170, 186,
+    17, 21, 81, 85, 86, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    17, 21, 81, 85, 86, 101, 102, 106, 166, 170,
+    17, 21, 22, 81, 82, 85, 86, 90, 101, 102, 103, 106, 107, 150, 166, 167, 170, 171,
+    20, 21, 84, 85, 89, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 166, 170,
+    21, 22, 85, 86, 90, 101, 102, 106, 107, 166, 170, 171,
+    20, 21, 84, 85, 89, 101, 105, 106, 169, 170,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 169, 170,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 170,
+    21, 22, 85, 86, 89, 90, 101, 102, 105, 106, 107, 170, 171,
+    20, 21, 25, 84, 85, 88, 89, 90, 101, 105, 106, 109, 110, 153, 169, 170, 173, 174,
+    21, 25, 85, 89, 90, 101, 105, 106, 110, 169, 170, 174,
+    21, 25, 85, 86, 89, 90, 101, 102, 105, 106, 110, 170, 174,
+    21, 22, 25, 26, 85, 86, 89, 90, 101, 102, 105, 106, 107, 110, 154, 170, 171, 174, 175,
+    16, 21, 37, 81, 84, 85, 97, 100, 101, 102, 105, 106, 117, 165, 166, 169, 170, 186,
+    17, 21, 37, 81, 85, 86, 97, 101, 102, 105, 106, 118, 122, 165, 166, 169, 170, 182, 186,
+    17, 21, 37, 81, 85, 86, 97, 101, 102, 105, 106, 118, 122, 165, 166, 170, 182, 186,
+    17, 21, 38, 81, 85, 86, 98, 101, 102, 103, 106, 118, 166, 170, 171, 186, 187,
+    20, 21, 37, 84, 85, 89, 100, 101, 102, 105, 106, 121, 122, 165, 166, 169, 170, 185, 186,
+    21, 37, 85, 101, 102, 105, 106, 122, 165, 166, 169, 170, 186,
+    21, 37, 85, 86, 101, 102, 105, 106, 122, 166, 170, 186,
+    21, 22, 37, 38, 85, 86, 90, 101, 102, 105, 106, 107, 122, 166, 170, 171, 186, 187,
+    20, 21, 37, 84, 85, 89, 100, 101, 102, 105, 106, 121, 122, 165, 169, 170, 185, 186,
+    21, 37, 85, 89, 101, 102, 105, 106, 122, 169, 170, 186,
+    21, 37, 85, 86, 89, 90, 101, 102, 105, 106, 122, 170, 186,
+    21, 22, 37, 38, 85, 86, 89, 90, 101, 102, 105, 106, 107, 122, 166, 170, 171, 186, 187,
+    20, 21, 41, 84, 85, 89, 101, 104, 105, 106, 109, 121, 169, 170, 174, 186, 190,
+    21, 25, 37, 41, 85, 89, 90, 101, 102, 105, 106, 110, 122, 169, 170, 174, 186, 190,
+    21, 25, 37, 41, 85, 86, 89, 90, 101, 102, 105, 106, 110, 122, 169, 170, 174, 186, 190,
+    21, 42, 85, 86, 89, 90, 101, 102, 105, 106, 107, 110, 122, 170, 171, 174, 186, 191,
+    64, 65, 68, 69, 80, 81, 84, 85, 86, 89, 101, 149, 150, 153, 154, 165, 166, 169, 170,
+    65, 69, 81, 85, 86, 89, 90, 101, 102, 149, 150, 153, 154, 165, 166, 170,
+    65, 69, 81, 85, 86, 90, 102, 106, 149, 150, 154, 166, 170, 171,
+    65, 69, 70, 81, 82, 85, 86, 90, 102, 106, 149, 150, 151, 154, 155, 166, 167, 170, 171,
+    68, 69, 84, 85, 86, 89, 90, 101, 105, 149, 150, 153, 154, 165, 169, 170,
+    69, 85, 86, 89, 90, 101, 106, 149, 150, 153, 154, 166, 169, 170,
+    69, 85, 86, 89, 90, 102, 106, 149, 150, 153, 154, 166, 170, 171,
+    69, 70, 85, 86, 90, 102, 106, 150, 154, 155, 166, 170, 171,
+    68, 69, 84, 85, 89, 90, 105, 106, 149, 153, 154, 169, 170, 174,
+    69, 85, 86, 89, 90, 105, 106, 149, 150, 153, 154, 169, 170, 174,
+    69, 85, 86, 89, 90, 106, 149, 150, 153, 154, 170,
+    69, 70, 85, 86, 89, 90, 106, 150, 154, 155, 170, 171,
+    68, 69, 73, 84, 85, 88, 89, 90, 105, 106, 149, 153, 154, 157, 158, 169, 170, 173, 174,
+    69, 73, 85, 89, 90, 105, 106, 153, 154, 158, 169, 170, 174,
+    69, 73, 85, 86, 89, 90, 106, 153, 154, 158, 170, 174,
+    69, 70, 73, 74, 85, 86, 89, 90, 106, 150, 153, 154, 155, 158, 170, 171, 174, 175,
+    80, 81, 84, 85, 86, 89, 101, 102, 105, 149, 150, 153, 165, 166, 169, 170,
+    81, 85, 86, 89, 101, 102, 106, 149, 150, 154, 165, 166, 169, 170,
+    81, 85, 86, 90, 101, 102, 106, 149, 150, 154, 165, 166, 170, 171,
+    81, 82, 85, 86, 90, 102, 106, 150, 154, 166, 167, 170, 171,
+    84, 85, 86, 89, 101, 105, 106, 149, 153, 154, 165, 166, 169, 170,
+    21, 69, 81, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170,
+    21, 69, 81, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171,
+    85, 86, 90, 102, 106, 150, 154, 166, 170, 171,
+    84, 85, 89, 90, 101, 105, 106, 149, 153, 154, 165, 169, 170, 174,
+    21, 69, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 174,
+    21, 69, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 166, 169, 170, 171, 174,
+    85, 86, 89, 90, 102, 106, 150, 154, 166, 170, 171,
+    84, 85, 88, 89, 90, 105, 106, 153, 154, 169, 170, 173, 174,
+    85, 89, 90, 105, 106, 153, 154, 169, 170, 174,
+    85, 86, 89, 90, 105, 106, 153, 154, 169, 170, 174,
+    85, 86, 89, 90, 106, 154, 170, 171, 174, 175,
+    80, 81, 84, 85, 101, 102, 105, 106, 149, 165, 166, 169, 170, 186,
+    81, 85, 86, 101, 102, 105, 106, 149, 150, 165, 166, 169, 170, 186,
+    81, 85, 86, 101, 102, 106, 149, 150, 165, 166, 170,
+    81, 82, 85, 86, 101, 102, 106, 150, 166, 167, 170, 171,
+    84, 85, 89, 101, 102, 105, 106, 149, 153, 165, 166, 169, 170, 186,
+    21, 81, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 186,
+    21, 81, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 154, 165, 166, 169, 170, 171, 186,
+    85, 86, 90, 101, 102, 106, 150, 154, 166, 170, 171,
+    84, 85, 89, 101, 105, 106, 149, 153, 165, 169, 170,
+    21, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 153, 154, 165, 166, 169, 170, 174, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 174, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 150, 154, 166, 170, 171,
+    84, 85, 88, 89, 101, 105, 106, 153, 169, 170, 173, 174,
+    85, 89, 90, 101, 105, 106, 153, 154, 169, 170, 174,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 153, 154, 169, 170, 174,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 154, 170, 171, 174, 175,
+    80, 81, 84, 85, 97, 100, 101, 102, 105, 106, 149, 165, 166, 169, 170, 181, 182, 185, 186,
+    81, 85, 97, 101, 102, 105, 106, 165, 166, 169, 170, 182, 186,
+    81, 85, 86, 97, 101, 102, 106, 165, 166, 170, 182, 186,
+    81, 82, 85, 86, 97, 98, 101, 102, 106, 150, 165, 166, 167, 170, 171, 182, 186, 187,
+    84, 85, 100, 101, 102, 105, 106, 165, 166, 169, 170, 185, 186,
+    85, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    85, 86, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    85, 86, 101, 102, 106, 166, 170, 171, 186, 187,
+    84, 85, 89, 100, 101, 105, 106, 165, 169, 170, 185, 186,
+    85, 89, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 165, 166, 169, 170, 186,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 166, 170, 171, 186, 187,
+    84, 85, 88, 89, 100, 101, 104, 105, 106, 153, 165, 169, 170, 173, 174, 185, 186, 190,
+    85, 89, 101, 105, 106, 169, 170, 174, 186, 190,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 169, 170, 174, 186, 190,
+    21, 85, 86, 89, 90, 101, 102, 105, 106, 154, 166, 169, 170, 171, 174, 175, 186, 187, 190, 191,
+    64, 65, 68, 69, 80, 81, 84, 85, 86, 89, 101, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    65, 69, 81, 85, 86, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    65, 69, 81, 85, 86, 149, 150, 154, 166, 170,
+    65, 69, 70, 81, 82, 85, 86, 90, 102, 149, 150, 151, 154, 155, 166, 167, 170, 171,
+    68, 69, 84, 85, 89, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 166, 170,
+    69, 70, 85, 86, 90, 149, 150, 154, 155, 166, 170, 171,
+    68, 69, 84, 85, 89, 149, 153, 154, 169, 170,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 169, 170,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 170,
+    69, 70, 85, 86, 89, 90, 149, 150, 153, 154, 155, 170, 171,
+    68, 69, 73, 84, 85, 88, 89, 90, 105, 149, 153, 154, 157, 158, 169, 170, 173, 174,
+    69, 73, 85, 89, 90, 149, 153, 154, 158, 169, 170, 174,
+    69, 73, 85, 86, 89, 90, 149, 150, 153, 154, 158, 170, 174,
+    69, 70, 73, 74, 85, 86, 89, 90, 106, 149, 150, 153, 154, 155, 158, 170, 171, 174, 175,
+    80, 81, 84, 85, 101, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    81, 85, 86, 101, 102, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    81, 85, 86, 101, 102, 149, 150, 154, 165, 166, 170,
+    81, 82, 85, 86, 102, 149, 150, 154, 166, 167, 170, 171,
+    84, 85, 89, 101, 105, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 81, 84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 81, 85, 86, 89, 90, 101, 102, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 234,
+    85, 86, 90, 102, 106, 149, 150, 154, 166, 170, 171,
+    84, 85, 89, 101, 105, 149, 153, 154, 165, 169, 170,
+    69, 84, 85, 86, 89, 90, 101, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 174, 234,
+    69, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 174, 234,
+    69, 85, 86, 89, 90, 102, 106, 149, 150, 153, 154, 166, 170, 171,
+    84, 85, 88, 89, 105, 149, 153, 154, 169, 170, 173, 174,
+    85, 89, 90, 105, 106, 149, 153, 154, 169, 170, 174,
+    69, 85, 86, 89, 90, 105, 106, 149, 150, 153, 154, 169, 170, 174,
+    69, 85, 86, 89, 90, 106, 149, 150, 153, 154, 170, 171, 174, 175,
+    80, 81, 84, 85, 101, 149, 165, 166, 169, 170,
+    81, 85, 86, 101, 102, 149, 150, 165, 166, 169, 170,
+    81, 85, 86, 101, 102, 149, 150, 165, 166, 170,
+    81, 82, 85, 86, 101, 102, 149, 150, 165, 166, 167, 170, 171,
+    84, 85, 89, 101, 105, 149, 153, 165, 166, 169, 170,
+    81, 84, 85, 86, 89, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 186, 234,
+    81, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 186, 234,
+    81, 85, 86, 90, 101, 102, 106, 149, 150, 154, 165, 166, 170, 171,
+    84, 85, 89, 101, 105, 149, 153, 165, 169, 170,
+    84, 85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 174, 186, 234,
+    85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 174, 186, 234,
+    85, 86, 89, 90, 101, 102, 106, 149, 150, 154, 166, 169, 170, 171,
+    84, 85, 88, 89, 101, 105, 149, 153, 165, 169, 170, 173, 174,
+    84, 85, 89, 90, 101, 105, 106, 149, 153, 154, 165, 169, 170, 174,
+    85, 86, 89, 90, 101, 105, 106, 149, 153, 154, 166, 169, 170, 174,
+    85, 86, 89, 90, 102, 105, 106, 150, 153, 154, 166, 169, 170, 171, 174, 175,
+    80, 81, 84, 85, 97, 100, 101, 102, 105, 149, 165, 166, 169, 170, 181, 182, 185, 186,
+    81, 85, 97, 101, 102, 149, 165, 166, 169, 170, 182, 186,
+    81, 85, 86, 97, 101, 102, 149, 150, 165, 166, 170, 182, 186,
+    81, 82, 85, 86, 97, 98, 101, 102, 106, 149, 150, 165, 166, 167, 170, 171, 182, 186, 187,
+    84, 85, 100, 101, 105, 149, 165, 166, 169, 170, 185, 186,
+    85, 101, 102, 105, 106, 149, 165, 166, 169, 170, 186,
+    81, 85, 86, 101, 102, 105, 106, 149, 150, 165, 166, 169, 170, 186,
+    81, 85, 86, 101, 102, 106, 149, 150, 165, 166, 170, 171, 186, 187,
+    84, 85, 89, 100, 101, 105, 149, 153, 165, 169, 170, 185, 186,
+    84, 85, 89, 101, 102, 105, 106, 149, 153, 165, 166, 169, 170, 186,
+    85, 86, 89, 101, 102, 105, 106, 149, 154, 165, 166, 169, 170, 186,
+    85, 86, 90, 101, 102, 105, 106, 150, 154, 165, 166, 169, 170, 171, 186, 187,
+    84, 85, 88, 89, 100, 101, 104, 105, 106, 149, 153, 165, 169, 170, 173, 174, 185, 186, 190,
+    84, 85, 89, 101, 105, 106, 149, 153, 165, 169, 170, 174, 186, 190,
+    85, 89, 90, 101, 102, 105, 106, 153, 154, 165, 166, 169, 170, 174, 186, 190,
+    85, 86, 89, 90, 101, 102, 105, 106, 154, 166, 169, 170, 171, 174, 175, 186, 187, 190, 191,
+    64, 69, 81, 84, 85, 133, 145, 148, 149, 150, 153, 154, 165, 166, 169, 170, 213, 234,
+    65, 69, 81, 85, 86, 133, 145, 149, 150, 153, 154, 165, 166, 169, 170, 214, 218, 230, 234,
+    65, 69, 81, 85, 86, 133, 145, 149, 150, 153, 154, 165, 166, 170, 214, 218, 230, 234,
+    65, 69, 81, 85, 86, 134, 146, 149, 150, 151, 154, 166, 170, 171, 214, 234, 235,
+    68, 69, 84, 85, 89, 133, 148, 149, 150, 153, 154, 165, 166, 169, 170, 217, 218, 233, 234,
+    69, 85, 133, 149, 150, 153, 154, 165, 166, 169, 170, 218, 234,
+    69, 85, 86, 133, 149, 150, 153, 154, 166, 170, 218, 234,
+    69, 70, 85, 86, 90, 133, 134, 149, 150, 153, 154, 155, 166, 170, 171, 218, 234, 235,
+    68, 69, 84, 85, 89, 133, 148, 149, 150, 153, 154, 165, 169, 170, 217, 218, 233, 234,
+    69, 85, 89, 133, 149, 150, 153, 154, 169, 170, 218, 234,
+    69, 85, 86, 89, 90, 133, 149, 150, 153, 154, 170, 218, 234,
+    69, 70, 85, 86, 89, 90, 133, 134, 149, 150, 153, 154, 155, 166, 170, 171, 218, 234, 235,
+    68, 69, 84, 85, 89, 137, 149, 152, 153, 154, 157, 169, 170, 174, 217, 234, 238,
+    69, 73, 85, 89, 90, 133, 137, 149, 150, 153, 154, 158, 169, 170, 174, 218, 234, 238,
+    69, 73, 85, 86, 89, 90, 133, 137, 149, 150, 153, 154, 158, 169, 170, 174, 218, 234, 238,
+    69, 85, 86, 89, 90, 138, 149, 150, 153, 154, 155, 158, 170, 171, 174, 218, 234, 239,
+    80, 81, 84, 85, 101, 145, 148, 149, 150, 153, 154, 165, 166, 169, 170, 229, 230, 233, 234,
+    81, 85, 145, 149, 150, 153, 154, 165, 166, 169, 170, 230, 234,
+    81, 85, 86, 145, 149, 150, 154, 165, 166, 170, 230, 234,
+    81, 82, 85, 86, 102, 145, 146, 149, 150, 154, 165, 166, 167, 170, 171, 230, 234, 235,
+    84, 85, 148, 149, 150, 153, 154, 165, 166, 169, 170, 233, 234,
+    85, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    85, 86, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    85, 86, 149, 150, 154, 166, 170, 171, 234, 235,
+    84, 85, 89, 148, 149, 153, 154, 165, 169, 170, 233, 234,
+    85, 89, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 166, 170, 171, 234, 235,
+    84, 85, 88, 89, 105, 148, 149, 152, 153, 154, 165, 169, 170, 173, 174, 233, 234, 238,
+    85, 89, 149, 153, 154, 169, 170, 174, 234, 238,
+    69, 85, 86, 89, 90, 149, 150, 153, 154, 169, 170, 174, 234, 238,
+    69, 85, 86, 89, 90, 106, 149, 150, 153, 154, 166, 169, 170, 171, 174, 175, 234, 235, 238, 239,
+    80, 81, 84, 85, 101, 145, 148, 149, 150, 153, 165, 166, 169, 170, 229, 230, 233, 234,
+    81, 85, 101, 145, 149, 150, 165, 166, 169, 170, 230, 234,
+    81, 85, 86, 101, 102, 145, 149, 150, 165, 166, 170, 230, 234,
+    81, 82, 85, 86, 101, 102, 145, 146, 149, 150, 154, 165, 166, 167, 170, 171, 230, 234, 235,
+    84, 85, 101, 148, 149, 153, 165, 166, 169, 170, 233, 234,
+    85, 101, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    81, 85, 86, 101, 102, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    81, 85, 86, 101, 102, 149, 150, 154, 165, 166, 170, 171, 234, 235,
+    84, 85, 89, 101, 105, 148, 149, 153, 165, 169, 170, 233, 234,
+    84, 85, 89, 101, 105, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    85, 86, 89, 101, 106, 149, 150, 153, 154, 165, 166, 169, 170, 234,
+    85, 86, 90, 102, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 234, 235,
+    84, 85, 88, 89, 101, 105, 148, 149, 152, 153, 154, 165, 169, 170, 173, 174, 233, 234, 238,
+    84, 85, 89, 101, 105, 149, 153, 154, 165, 169, 170, 174, 234, 238,
+    85, 89, 90, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 174, 234, 238,
+    85, 86, 89, 90, 106, 149, 150, 153, 154, 166, 169, 170, 171, 174, 175, 234, 235, 238, 239,
+    80, 81, 84, 85, 101, 149, 161, 164, 165, 166, 169, 170, 181, 186, 229, 234, 250,
+    81, 85, 97, 101, 102, 145, 149, 150, 161, 165, 166, 169, 170, 182, 186, 230, 234, 250,
+    81, 85, 86, 97, 101, 102, 145, 149, 150, 161, 165, 166, 169, 170, 182, 186, 230, 234, 250,
+    81, 85, 86, 101, 102, 149, 150, 162, 165, 166, 167, 170, 171, 182, 186, 230, 234, 251,
+    84, 85, 100, 101, 105, 148, 149, 153, 164, 165, 166, 169, 170, 185, 186, 233, 234, 250,
+    85, 101, 149, 165, 166, 169, 170, 186, 234, 250,
+    81, 85, 86, 101, 102, 149, 150, 165, 166, 169, 170, 186, 234, 250,
+    81, 85, 86, 101, 102, 106, 149, 150, 154, 165, 166, 169, 170, 171, 186, 187, 234, 235, 250, 251,
+    84, 85, 89, 100, 101, 105, 148, 149, 153, 164, 165, 166, 169, 170, 185, 186, 233, 234, 250,
+    84, 85, 89, 101, 105, 149, 153, 165, 166, 169, 170, 186, 234, 250,
+    85, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 186, 234, 250,
+    85, 86, 101, 102, 106, 149, 150, 154, 165, 166, 169, 170, 171, 186, 187, 234, 235, 250, 251,
+    84, 85, 89, 101, 105, 149, 153, 165, 168, 169, 170, 173, 174, 185, 186, 233, 234, 254,
+    84, 85, 89, 101, 105, 106, 149, 153, 154, 165, 166, 169, 170, 174, 186, 190, 234, 238, 250, 254,
+    85, 89, 101, 105, 106, 149, 153, 154, 165, 166, 169, 170, 174, 186, 190, 234, 238, 250, 254,
+    85, 86, 89, 90, 101, 102, 105, 106, 149, 150, 153, 154, 165, 166, 169, 170, 171, 174, 186, 234, 255,
+];
+
+/// OpenSimplex2 noise generator, the fast variant.
+///
+/// Each call evaluates one octave and matches the output of the
+/// reference implementation for the same seed.
+pub struct OpenSimplex2 {
+    /// Seeds the gradients hash.
+    seed: i64,
+}
+
+impl OpenSimplex2 {
+    /// Creates an OpenSimplex2 noise generator with a zero seed.
+    pub fn new() -> OpenSimplex2 {
+        OpenSimplex2 { seed: 0 }
+    }
+
+    /// Creates an OpenSimplex2 noise generator with the given seed.
+    pub fn with_seed(seed:u64) -> OpenSimplex2 {
+        OpenSimplex2 { seed: seed as i64 }
+    }
+
+    /// Sets the seed.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed as i64;
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed as u64
+    }
+
+    /// Returns the noise value at the point(x,y,z), oriented so that
+    /// xy slices look best. Use it when z is time or the vertical axis.
+    pub fn get_value_3d_xy(&self, x:f32, y:f32, z:f32) -> f32 {
+        let (xr, yr, zr) = rotate_3d_xy(x as f64, y as f64, z as f64);
+        self.noise_3d_unrotated(xr, yr, zr) as f32
+    }
+
+    /// Generate one point 2D noise from skewed coordinates.
+    fn noise_2d_unskewed(&self, xs:f64, ys:f64) -> f64 {
+        let r_squared = 0.5;
+
+        // Find the base vertex of the lattice rhombus containing the point.
+        let xsb = fast_floor(xs);
+        let ysb = fast_floor(ys);
+        let xi = xs - xsb as f64;
+        let yi = ys - ysb as f64;
+        let xsbp = xsb as i64 * PRIME_X;
+        let ysbp = ysb as i64 * PRIME_Y;
+
+        // Unskew the offset from the base vertex.
+        let t = (xi + yi) * UNSKEW_2D;
+        let dx0 = xi + t;
+        let dy0 = yi + t;
+
+        // First vertex.
+        let mut value = 0.0;
+        let a0 = r_squared - dx0 * dx0 - dy0 * dy0;
+        if a0 > 0.0 {
+            value = (a0 * a0) * (a0 * a0) * grad_2d(&GRAD2, self.seed, xsbp, ysbp, dx0, dy0);
+        }
+
+        // Second vertex, opposite to the first one.
+        let a1 = (2.0 * (1.0 + 2.0 * UNSKEW_2D) * (1.0 / UNSKEW_2D + 2.0)) * t
+            + ((-2.0 * (1.0 + 2.0 * UNSKEW_2D) * (1.0 + 2.0 * UNSKEW_2D)) + a0);
+        if a1 > 0.0 {
+            let dx1 = dx0 - (1.0 + 2.0 * UNSKEW_2D);
+            let dy1 = dy0 - (1.0 + 2.0 * UNSKEW_2D);
+            value += (a1 * a1) * (a1 * a1)
+                * grad_2d(&GRAD2, self.seed, xsbp + PRIME_X, ysbp + PRIME_Y, dx1, dy1);
+        }
+
+        // Third vertex, on the side of the point.
+        if dy0 > dx0 {
+            let dx2 = dx0 - UNSKEW_2D;
+            let dy2 = dy0 - (UNSKEW_2D + 1.0);
+            let a2 = r_squared - dx2 * dx2 - dy2 * dy2;
+            if a2 > 0.0 {
+                value += (a2 * a2) * (a2 * a2)
+                    * grad_2d(&GRAD2, self.seed, xsbp, ysbp + PRIME_Y, dx2, dy2);
+            }
+        } else {
+            let dx2 = dx0 - (UNSKEW_2D + 1.0);
+            let dy2 = dy0 - UNSKEW_2D;
+            let a2 = r_squared - dx2 * dx2 - dy2 * dy2;
+            if a2 > 0.0 {
+                value += (a2 * a2) * (a2 * a2)
+                    * grad_2d(&GRAD2, self.seed, xsbp + PRIME_X, ysbp, dx2, dy2);
+            }
+        }
+        value
+    }
+
+    /// Generate one point 3D noise from rotated coordinates.
+    ///
+    /// The noise is made of two offset body centered cubic lattices,
+    /// each contributing its closest vertex and the next closest one.
+    fn noise_3d_unrotated(&self, xr:f64, yr:f64, zr:f64) -> f64 {
+        let r_squared = 0.6;
+        let mut seed = self.seed;
+
+        // Find the closest vertex on the first lattice.
+        let xrb = fast_round(xr);
+        let yrb = fast_round(yr);
+        let zrb = fast_round(zr);
+        let mut xri = xr - xrb as f64;
+        let mut yri = yr - yrb as f64;
+        let mut zri = zr - zrb as f64;
+
+        // -1 if the offset is positive, 1 otherwise.
+        let mut x_sign = (-1.0 - xri) as i32 | 1;
+        let mut y_sign = (-1.0 - yri) as i32 | 1;
+        let mut z_sign = (-1.0 - zri) as i32 | 1;
+
+        // Absolute offsets.
+        let mut ax0 = x_sign as f64 * -xri;
+        let mut ay0 = y_sign as f64 * -yri;
+        let mut az0 = z_sign as f64 * -zri;
+
+        let mut xrbp = xrb as i64 * PRIME_X;
+        let mut yrbp = yrb as i64 * PRIME_Y;
+        let mut zrbp = zrb as i64 * PRIME_Z;
+
+        let mut value = 0.0;
+        let mut a = (r_squared - xri * xri) - (yri * yri + zri * zri);
+        for lattice in range(0u, 2) {
+            // Closest vertex.
+            if a > 0.0 {
+                value += (a * a) * (a * a)
+                    * grad_3d(&GRAD3, seed, xrbp, yrbp, zrbp, xri, yri, zri);
+            }
+
+            // Next closest vertex, along the axis of the largest offset.
+            if ax0 >= ay0 && ax0 >= az0 {
+                let mut b = a + ax0 + ax0;
+                if b > 1.0 {
+                    b -= 1.0;
+                    value += (b * b) * (b * b)
+                        * grad_3d(&GRAD3, seed, xrbp - x_sign as i64 * PRIME_X, yrbp, zrbp,
+                                  xri + x_sign as f64, yri, zri);
+                }
+            } else if ay0 > ax0 && ay0 >= az0 {
+                let mut b = a + ay0 + ay0;
+                if b > 1.0 {
+                    b -= 1.0;
+                    value += (b * b) * (b * b)
+                        * grad_3d(&GRAD3, seed, xrbp, yrbp - y_sign as i64 * PRIME_Y, zrbp,
+                                  xri, yri + y_sign as f64, zri);
+                }
+            } else {
+                let mut b = a + az0 + az0;
+                if b > 1.0 {
+                    b -= 1.0;
+                    value += (b * b) * (b * b)
+                        * grad_3d(&GRAD3, seed, xrbp, yrbp, zrbp - z_sign as i64 * PRIME_Z,
+                                  xri, yri, zri + z_sign as f64);
+                }
+            }
+
+            if lattice == 1 {
+                break;
+            }
+
+            // Move to the closest vertex of the second lattice.
+            ax0 = 0.5 - ax0;
+            ay0 = 0.5 - ay0;
+            az0 = 0.5 - az0;
+
+            xri = x_sign as f64 * ax0;
+            yri = y_sign as f64 * ay0;
+            zri = z_sign as f64 * az0;
+
+            a += (0.75 - 0.5) - (ax0 + ay0 + az0);
+
+            xrbp += ((x_sign >> 1) as i64) & PRIME_X;
+            yrbp += ((y_sign >> 1) as i64) & PRIME_Y;
+            zrbp += ((z_sign >> 1) as i64) & PRIME_Z;
+
+            x_sign = -x_sign;
+            y_sign = -y_sign;
+            z_sign = -z_sign;
+
+            seed ^= SEED_FLIP_3D;
+        }
+        value
+    }
+
+    /// Generate one point 4D noise from skewed coordinates.
+    ///
+    /// The noise is made of five copies of the A4 lattice, each offset
+    /// along the main diagonal and contributing its closest vertex.
+    fn noise_4d_unskewed(&self, xs:f64, ys:f64, zs:f64, ws:f64) -> f64 {
+        let r_squared = 0.6;
+        let mut seed = self.seed;
+
+        // Find the base vertex of the hypercube containing the point.
+        let xsb = fast_floor(xs);
+        let ysb = fast_floor(ys);
+        let zsb = fast_floor(zs);
+        let wsb = fast_floor(ws);
+        let mut xsi = xs - xsb as f64;
+        let mut ysi = ys - ysb as f64;
+        let mut zsi = zs - zsb as f64;
+        let mut wsi = ws - wsb as f64;
+
+        // Start from the lattice copy the point is closest to, so that the
+        // vertex choice below never has to look back more than one step.
+        let si_sum = (xsi + ysi) + (zsi + wsi);
+        let starting_lattice = (si_sum * 1.25) as i64;
+        seed += starting_lattice * SEED_OFFSET_4D;
+        let starting_lattice_offset = starting_lattice as f64 * -LATTICE_STEP_4D;
+        xsi += starting_lattice_offset;
+        ysi += starting_lattice_offset;
+        zsi += starting_lattice_offset;
+        wsi += starting_lattice_offset;
+
+        // Unskew term of the offset.
+        let mut ssi = (si_sum + starting_lattice_offset * 4.0) * UNSKEW_4D;
+
+        let mut xsvp = xsb as i64 * PRIME_X;
+        let mut ysvp = ysb as i64 * PRIME_Y;
+        let mut zsvp = zsb as i64 * PRIME_Z;
+        let mut wsvp = wsb as i64 * PRIME_W;
+
+        let mut value = 0.0;
+        for lattice in range(0i64, 5) {
+            // Next the closest vertex on this lattice copy, moving along
+            // the axis of the largest offset if it beats the base vertex.
+            let score0 = 1.0 + ssi * (-1.0 / UNSKEW_4D);
+            if xsi >= ysi && xsi >= zsi && xsi >= wsi && xsi >= score0 {
+                xsvp += PRIME_X;
+                xsi -= 1.0;
+                ssi -= UNSKEW_4D;
+            } else if ysi > xsi && ysi >= zsi && ysi >= wsi && ysi >= score0 {
+                ysvp += PRIME_Y;
+                ysi -= 1.0;
+                ssi -= UNSKEW_4D;
+            } else if zsi > xsi && zsi > ysi && zsi >= wsi && zsi >= score0 {
+                zsvp += PRIME_Z;
+                zsi -= 1.0;
+                ssi -= UNSKEW_4D;
+            } else if wsi > xsi && wsi > ysi && wsi > zsi && wsi >= score0 {
+                wsvp += PRIME_W;
+                wsi -= 1.0;
+                ssi -= UNSKEW_4D;
+            }
+
+            // Its contribution.
+            let dx = xsi + ssi;
+            let dy = ysi + ssi;
+            let dz = zsi + ssi;
+            let dw = wsi + ssi;
+            let mut a = (dx * dx + dy * dy) + (dz * dz + dw * dw);
+            if a < r_squared {
+                a -= r_squared;
+                a *= a;
+                value += a * a * grad_4d(&GRAD4, seed, xsvp, ysvp, zsvp, wsvp, dx, dy, dz, dw);
+            }
+
+            if lattice == 4 {
+                break;
+            }
+
+            // Move to the next lattice copy.
+            xsi += LATTICE_STEP_4D;
+            ysi += LATTICE_STEP_4D;
+            zsi += LATTICE_STEP_4D;
+            wsi += LATTICE_STEP_4D;
+            ssi += LATTICE_STEP_4D * 4.0 * UNSKEW_4D;
+            seed -= SEED_OFFSET_4D;
+
+            // Wrap around to the first lattice copy.
+            if lattice == starting_lattice {
+                xsvp -= PRIME_X;
+                ysvp -= PRIME_Y;
+                zsvp -= PRIME_Z;
+                wsvp -= PRIME_W;
+                seed += SEED_OFFSET_4D * 5;
+            }
+        }
+        value
+    }
+}
+
+impl BasisNoise for OpenSimplex2 {
+    /// Returns the noise value at the point(x,y,z),
+    /// without favoring any plane.
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.get_value(x, y, z)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for OpenSimplex2 {
+    /// Returns the noise value at the point(x,y,z),
+    /// without favoring any plane.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let (xr, yr, zr) = rotate_3d_fallback(x as f64, y as f64, z as f64);
+        self.noise_3d_unrotated(xr, yr, zr) as f32
+    }
+
+    /// Returns the 2D noise value at the point(x,y).
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        let (xs, ys) = skew_2d(x as f64, y as f64);
+        self.noise_2d_unskewed(xs, ys) as f32
+    }
+}
+
+impl Noise4D for OpenSimplex2 {
+    /// Returns the noise value at the point(x,y,z,w),
+    /// without favoring any plane.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let (xs, ys, zs, ws) = skew_4d(SKEW_4D, x as f64, y as f64, z as f64, w as f64);
+        self.noise_4d_unskewed(xs, ys, zs, ws) as f32
+    }
+}
+
+/// OpenSimplex2S noise generator, the smooth variant.
+///
+/// It sums more vertices than `OpenSimplex2` for a smoother output.
+/// Each call evaluates one octave and matches the output of the
+/// reference implementation for the same seed.
+pub struct OpenSimplex2S {
+    /// Seeds the gradients hash.
+    seed: i64,
+}
+
+impl OpenSimplex2S {
+    /// Creates an OpenSimplex2S noise generator with a zero seed.
+    pub fn new() -> OpenSimplex2S {
+        OpenSimplex2S { seed: 0 }
+    }
+
+    /// Creates an OpenSimplex2S noise generator with the given seed.
+    pub fn with_seed(seed:u64) -> OpenSimplex2S {
+        OpenSimplex2S { seed: seed as i64 }
+    }
+
+    /// Sets the seed.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed as i64;
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed as u64
+    }
+
+    /// Returns the noise value at the point(x,y,z), oriented so that
+    /// xy slices look best. Use it when z is time or the vertical axis.
+    pub fn get_value_3d_xy(&self, x:f32, y:f32, z:f32) -> f32 {
+        let (xr, yr, zr) = rotate_3d_xy(x as f64, y as f64, z as f64);
+        self.noise_3d_unrotated(xr, yr, zr) as f32
+    }
+
+    /// Generate one point 2D noise from skewed coordinates.
+    fn noise_2d_unskewed(&self, xs:f64, ys:f64) -> f64 {
+        let seed = self.seed;
+
+        // Find the base vertex of the lattice rhombus containing the point.
+        let xsb = fast_floor(xs);
+        let ysb = fast_floor(ys);
+        let xi = xs - xsb as f64;
+        let yi = ys - ysb as f64;
+        let xsbp = xsb as i64 * PRIME_X;
+        let ysbp = ysb as i64 * PRIME_Y;
+
+        // Unskew the offset from the base vertex.
+        let t = (xi + yi) * UNSKEW_2D;
+        let dx0 = xi + t;
+        let dy0 = yi + t;
+
+        // The two vertices of the rhombus diagonal always contribute.
+        let a0 = (2.0 / 3.0) - dx0 * dx0 - dy0 * dy0;
+        let mut value = (a0 * a0) * (a0 * a0)
+            * grad_2d(&GRAD2_SMOOTH, seed, xsbp, ysbp, dx0, dy0);
+
+        let a1 = (2.0 * (1.0 + 2.0 * UNSKEW_2D) * (1.0 / UNSKEW_2D + 2.0)) * t
+            + ((-2.0 * (1.0 + 2.0 * UNSKEW_2D) * (1.0 + 2.0 * UNSKEW_2D)) + a0);
+        let dx1 = dx0 - (1.0 + 2.0 * UNSKEW_2D);
+        let dy1 = dy0 - (1.0 + 2.0 * UNSKEW_2D);
+        value += (a1 * a1) * (a1 * a1)
+            * grad_2d(&GRAD2_SMOOTH, seed, xsbp + PRIME_X, ysbp + PRIME_Y, dx1, dy1);
+
+        // Then two more vertices, chosen from the position in the rhombus.
+        let xmyi = xi - yi;
+        if t < UNSKEW_2D {
+            if xi + xmyi > 1.0 {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp + (PRIME_X << 1), ysbp + PRIME_Y,
+                                                  dx0 - (3.0 * UNSKEW_2D + 2.0),
+                                                  dy0 - (3.0 * UNSKEW_2D + 1.0));
+            } else {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp, ysbp + PRIME_Y,
+                                                  dx0 - UNSKEW_2D,
+                                                  dy0 - (UNSKEW_2D + 1.0));
+            }
+            if yi - xmyi > 1.0 {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp + PRIME_X, ysbp + (PRIME_Y << 1),
+                                                  dx0 - (3.0 * UNSKEW_2D + 1.0),
+                                                  dy0 - (3.0 * UNSKEW_2D + 2.0));
+            } else {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp + PRIME_X, ysbp,
+                                                  dx0 - (UNSKEW_2D + 1.0),
+                                                  dy0 - UNSKEW_2D);
+            }
+        } else {
+            if xi + xmyi < 0.0 {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp - PRIME_X, ysbp,
+                                                  dx0 + (1.0 + UNSKEW_2D),
+                                                  dy0 + UNSKEW_2D);
+            } else {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp + PRIME_X, ysbp,
+                                                  dx0 - (UNSKEW_2D + 1.0),
+                                                  dy0 - UNSKEW_2D);
+            }
+            if yi < xmyi {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp, ysbp - PRIME_Y,
+                                                  dx0 + UNSKEW_2D,
+                                                  dy0 + (UNSKEW_2D + 1.0));
+            } else {
+                value += OpenSimplex2S::vertex_2d(seed, xsbp, ysbp + PRIME_Y,
+                                                  dx0 - UNSKEW_2D,
+                                                  dy0 - (UNSKEW_2D + 1.0));
+            }
+        }
+        value
+    }
+
+    /// Contribution of a 2D vertex at offset (dx,dy).
+    fn vertex_2d(seed:i64, xsvp:i64, ysvp:i64, dx:f64, dy:f64) -> f64 {
+        let a = (2.0 / 3.0) - dx * dx - dy * dy;
+        if a > 0.0 {
+            (a * a) * (a * a) * grad_2d(&GRAD2_SMOOTH, seed, xsvp, ysvp, dx, dy)
+        } else {
+            0.0
+        }
+    }
+
+    /// Generate one point 3D noise from rotated coordinates.
+    ///
+    /// The noise is made of two offset body centered cubic lattices.
+    /// The closest vertex of each lattice always contributes, then the
+    /// neighbours are picked from the position of the point in the cube.
+    fn noise_3d_unrotated(&self, xr:f64, yr:f64, zr:f64) -> f64 {
+        let seed = self.seed;
+        let seed2 = seed ^ SEED_FLIP_3D;
+
+        // Find the base vertex of the cube containing the point.
+        let xrb = fast_floor(xr);
+        let yrb = fast_floor(yr);
+        let zrb = fast_floor(zr);
+        let xi = xr - xrb as f64;
+        let yi = yr - yrb as f64;
+        let zi = zr - zrb as f64;
+        let xrbp = xrb as i64 * PRIME_X;
+        let yrbp = yrb as i64 * PRIME_Y;
+        let zrbp = zrb as i64 * PRIME_Z;
+
+        // -1 if the point is in the lower half of the cube on an axis, 0 otherwise.
+        let x_mask = (-0.5 - xi) as i32;
+        let y_mask = (-0.5 - yi) as i32;
+        let z_mask = (-0.5 - zi) as i32;
+        let (xm, ym, zm) = (x_mask as i64, y_mask as i64, z_mask as i64);
+        // -1 or 1, the direction to flip an axis towards.
+        let x_sign = (x_mask | 1) as f64;
+        let y_sign = (y_mask | 1) as f64;
+        let z_sign = (z_mask | 1) as f64;
+
+        // Closest vertex of the first lattice.
+        let x0 = xi + x_mask as f64;
+        let y0 = yi + y_mask as f64;
+        let z0 = zi + z_mask as f64;
+        let a0 = 0.75 - x0 * x0 - y0 * y0 - z0 * z0;
+        let mut value = (a0 * a0) * (a0 * a0)
+            * grad_3d(&GRAD3_SMOOTH, seed,
+                      xrbp + (xm & PRIME_X), yrbp + (ym & PRIME_Y), zrbp + (zm & PRIME_Z),
+                      x0, y0, z0);
+
+        // Closest vertex of the second lattice, the center of the cube.
+        let x1 = xi - 0.5;
+        let y1 = yi - 0.5;
+        let z1 = zi - 0.5;
+        let a1 = 0.75 - x1 * x1 - y1 * y1 - z1 * z1;
+        value += (a1 * a1) * (a1 * a1)
+            * grad_3d(&GRAD3_SMOOTH, seed2,
+                      xrbp + PRIME_X, yrbp + PRIME_Y, zrbp + PRIME_Z,
+                      x1, y1, z1);
+
+        // Falloff changes when flipping an axis, on each lattice.
+        let x_flip0 = (((x_mask | 1) << 1) as f64) * x1;
+        let y_flip0 = (((y_mask | 1) << 1) as f64) * y1;
+        let z_flip0 = (((z_mask | 1) << 1) as f64) * z1;
+        let x_flip1 = ((-2 - (x_mask << 2)) as f64) * x1 - 1.0;
+        let y_flip1 = ((-2 - (y_mask << 2)) as f64) * y1 - 1.0;
+        let z_flip1 = ((-2 - (z_mask << 2)) as f64) * z1 - 1.0;
+
+        let mut skip_5 = false;
+        let a2 = x_flip0 + a0;
+        if a2 > 0.0 {
+            value += (a2 * a2) * (a2 * a2)
+                * grad_3d(&GRAD3_SMOOTH, seed,
+                          xrbp + (!xm & PRIME_X), yrbp + (ym & PRIME_Y), zrbp + (zm & PRIME_Z),
+                          x0 - x_sign, y0, z0);
+        } else {
+            let a3 = y_flip0 + z_flip0 + a0;
+            if a3 > 0.0 {
+                value += (a3 * a3) * (a3 * a3)
+                    * grad_3d(&GRAD3_SMOOTH, seed,
+                              xrbp + (xm & PRIME_X), yrbp + (!ym & PRIME_Y), zrbp + (!zm & PRIME_Z),
+                              x0, y0 - y_sign, z0 - z_sign);
+            }
+
+            let a4 = x_flip1 + a1;
+            if a4 > 0.0 {
+                value += (a4 * a4) * (a4 * a4)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + (xm & (PRIME_X << 1)), yrbp + PRIME_Y, zrbp + PRIME_Z,
+                              x_sign + x1, y1, z1);
+                skip_5 = true;
+            }
+        }
+
+        let mut skip_9 = false;
+        let a6 = y_flip0 + a0;
+        if a6 > 0.0 {
+            value += (a6 * a6) * (a6 * a6)
+                * grad_3d(&GRAD3_SMOOTH, seed,
+                          xrbp + (xm & PRIME_X), yrbp + (!ym & PRIME_Y), zrbp + (zm & PRIME_Z),
+                          x0, y0 - y_sign, z0);
+        } else {
+            let a7 = x_flip0 + z_flip0 + a0;
+            if a7 > 0.0 {
+                value += (a7 * a7) * (a7 * a7)
+                    * grad_3d(&GRAD3_SMOOTH, seed,
+                              xrbp + (!xm & PRIME_X), yrbp + (ym & PRIME_Y), zrbp + (!zm & PRIME_Z),
+                              x0 - x_sign, y0, z0 - z_sign);
+            }
+
+            let a8 = y_flip1 + a1;
+            if a8 > 0.0 {
+                value += (a8 * a8) * (a8 * a8)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + PRIME_X, yrbp + (ym & (PRIME_Y << 1)), zrbp + PRIME_Z,
+                              x1, y_sign + y1, z1);
+                skip_9 = true;
+            }
+        }
+
+        let mut skip_d = false;
+        let a_a = z_flip0 + a0;
+        if a_a > 0.0 {
+            value += (a_a * a_a) * (a_a * a_a)
+                * grad_3d(&GRAD3_SMOOTH, seed,
+                          xrbp + (xm & PRIME_X), yrbp + (ym & PRIME_Y), zrbp + (!zm & PRIME_Z),
+                          x0, y0, z0 - z_sign);
+        } else {
+            let a_b = x_flip0 + y_flip0 + a0;
+            if a_b > 0.0 {
+                value += (a_b * a_b) * (a_b * a_b)
+                    * grad_3d(&GRAD3_SMOOTH, seed,
+                              xrbp + (!xm & PRIME_X), yrbp + (!ym & PRIME_Y), zrbp + (zm & PRIME_Z),
+                              x0 - x_sign, y0 - y_sign, z0);
+            }
+
+            let a_c = z_flip1 + a1;
+            if a_c > 0.0 {
+                value += (a_c * a_c) * (a_c * a_c)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + PRIME_X, yrbp + PRIME_Y, zrbp + (zm & (PRIME_Z << 1)),
+                              x1, y1, z_sign + z1);
+                skip_d = true;
+            }
+        }
+
+        if !skip_5 {
+            let a5 = y_flip1 + z_flip1 + a1;
+            if a5 > 0.0 {
+                value += (a5 * a5) * (a5 * a5)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + PRIME_X, yrbp + (ym & (PRIME_Y << 1)), zrbp + (zm & (PRIME_Z << 1)),
+                              x1, y_sign + y1, z_sign + z1);
+            }
+        }
+
+        if !skip_9 {
+            let a9 = x_flip1 + z_flip1 + a1;
+            if a9 > 0.0 {
+                value += (a9 * a9) * (a9 * a9)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + (xm & (PRIME_X << 1)), yrbp + PRIME_Y, zrbp + (zm & (PRIME_Z << 1)),
+                              x_sign + x1, y1, z_sign + z1);
+            }
+        }
+
+        if !skip_d {
+            let a_d = x_flip1 + y_flip1 + a1;
+            if a_d > 0.0 {
+                value += (a_d * a_d) * (a_d * a_d)
+                    * grad_3d(&GRAD3_SMOOTH, seed2,
+                              xrbp + (xm & (PRIME_X << 1)), yrbp + (ym & (PRIME_Y << 1)), zrbp + PRIME_Z,
+                              x_sign + x1, y_sign + y1, z1);
+            }
+        }
+
+        value
+    }
+
+    /// Generate one point 4D noise from skewed coordinates.
+    ///
+    /// The skewed unit hypercube is split into 4x4x4x4 sub-cubes, each
+    /// with the precomputed list of the vertices that can reach it.
+    fn noise_4d_unskewed(&self, xs:f64, ys:f64, zs:f64, ws:f64) -> f64 {
+        let r_squared = 0.8;
+
+        // Find the base vertex of the hypercube containing the point.
+        let xsb = fast_floor(xs);
+        let ysb = fast_floor(ys);
+        let zsb = fast_floor(zs);
+        let wsb = fast_floor(ws);
+        let xsi = xs - xsb as f64;
+        let ysi = ys - ysb as f64;
+        let zsi = zs - zsb as f64;
+        let wsi = ws - wsb as f64;
+
+        // Unskew the offset from the base vertex.
+        let ssi = (xsi + ysi + zsi + wsi) * UNSKEW_4D_SMOOTH;
+        let xi = xsi + ssi;
+        let yi = ysi + ssi;
+        let zi = zsi + ssi;
+        let wi = wsi + ssi;
+
+        let xsvp = xsb as i64 * PRIME_X;
+        let ysvp = ysb as i64 * PRIME_Y;
+        let zsvp = zsb as i64 * PRIME_Z;
+        let wsvp = wsb as i64 * PRIME_W;
+
+        // Sub-cube of the point.
+        let index = ((fast_floor(xs * 4.0) & 3) << 0)
+            | ((fast_floor(ys * 4.0) & 3) << 2)
+            | ((fast_floor(zs * 4.0) & 3) << 4)
+            | ((fast_floor(ws * 4.0) & 3) << 6);
+
+        let mut value = 0.0;
+        let start = LOOKUP_4D_START[index as uint] as uint;
+        let end = LOOKUP_4D_START[index as uint + 1] as uint;
+        for &vertex in LOOKUP_4D_VERTICES.slice(start, end).iter() {
+            let xsv = (vertex & 3) as i64 - 1;
+            let ysv = ((vertex >> 2) & 3) as i64 - 1;
+            let zsv = ((vertex >> 4) & 3) as i64 - 1;
+            let wsv = ((vertex >> 6) & 3) as i64 - 1;
+
+            // Offset from the vertex, unskewed.
+            let ssv = (xsv + ysv + zsv + wsv) as f64 * UNSKEW_4D_SMOOTH;
+            let dx = xi - xsv as f64 - ssv;
+            let dy = yi - ysv as f64 - ssv;
+            let dz = zi - zsv as f64 - ssv;
+            let dw = wi - wsv as f64 - ssv;
+
+            let mut a = (dx * dx + dy * dy) + (dz * dz + dw * dw);
+            if a < r_squared {
+                a -= r_squared;
+                a *= a;
+                value += a * a * grad_4d(&GRAD4_SMOOTH, self.seed,
+                                         xsvp + xsv * PRIME_X, ysvp + ysv * PRIME_Y,
+                                         zsvp + zsv * PRIME_Z, wsvp + wsv * PRIME_W,
+                                         dx, dy, dz, dw);
+            }
+        }
+        value
+    }
+}
+
+impl BasisNoise for OpenSimplex2S {
+    /// Returns the noise value at the point(x,y,z),
+    /// without favoring any plane.
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.get_value(x, y, z)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for OpenSimplex2S {
+    /// Returns the noise value at the point(x,y,z),
+    /// without favoring any plane.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let (xr, yr, zr) = rotate_3d_fallback(x as f64, y as f64, z as f64);
+        self.noise_3d_unrotated(xr, yr, zr) as f32
+    }
+
+    /// Returns the 2D noise value at the point(x,y).
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        let (xs, ys) = skew_2d(x as f64, y as f64);
+        self.noise_2d_unskewed(xs, ys) as f32
+    }
+}
+
+impl Noise4D for OpenSimplex2S {
+    /// Returns the noise value at the point(x,y,z,w),
+    /// without favoring any plane.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let (xs, ys, zs, ws) = skew_4d(SKEW_4D_SMOOTH, x as f64, y as f64, z as f64, w as f64);
+        self.noise_4d_unskewed(xs, ys, zs, ws) as f32
+    }
+}
+
+/// Skews 2D coordinates onto the triangular lattice.
+fn skew_2d(x:f64, y:f64) -> (f64, f64) {
+    let s = SKEW_2D * (x + y);
+    (x + s, y + s)
+}
+
+/// Rotates 3D coordinates so that the lattice diagonal is along z.
+fn rotate_3d_xy(x:f64, y:f64, z:f64) -> (f64, f64, f64) {
+    let xy = x + y;
+    let s2 = xy * UNSKEW_2D;
+    let zz = z * ROOT3OVER3;
+    (x + s2 + zz, y + s2 + zz, xy * -ROOT3OVER3 + zz)
+}
+
+/// Rotates 3D coordinates without favoring any plane.
+fn rotate_3d_fallback(x:f64, y:f64, z:f64) -> (f64, f64, f64) {
+    let r = FALLBACK_ROTATE_3D * (x + y + z);
+    (r - x, r - y, r - z)
+}
+
+/// Skews 4D coordinates onto the lattice, without favoring any plane.
+fn skew_4d(skew:f64, x:f64, y:f64, z:f64, w:f64) -> (f64, f64, f64, f64) {
+    let s = skew * (x + y + z + w);
+    (x + s, y + s, z + s, w + s)
+}
+
+/// Hashes a 2D lattice vertex and dots its gradient with the offset.
+fn grad_2d(grads:&[[f64, ..2], ..24], seed:i64, xsvp:i64, ysvp:i64, dx:f64, dy:f64) -> f64 {
+    let mut hash = seed ^ xsvp ^ ysvp;
+    hash *= HASH_MULTIPLIER;
+    hash ^= hash >> 58;
+    let g = &grads[(((hash & 0xFE) >> 1) as uint) % 24];
+    g[0] * dx + g[1] * dy
+}
+
+/// Hashes a 3D lattice vertex and dots its gradient with the offset.
+fn grad_3d(grads:&[[f64, ..3], ..48], seed:i64, xrvp:i64, yrvp:i64, zrvp:i64,
+           dx:f64, dy:f64, dz:f64) -> f64 {
+    let mut hash = (seed ^ xrvp) ^ (yrvp ^ zrvp);
+    hash *= HASH_MULTIPLIER;
+    hash ^= hash >> 58;
+    let g = &grads[(((hash & 0x3FC) >> 2) as uint) % 48];
+    g[0] * dx + g[1] * dy + g[2] * dz
+}
+
+/// Hashes a 4D lattice vertex and dots its gradient with the offset.
+fn grad_4d(grads:&[[f64, ..4], ..160], seed:i64, xsvp:i64, ysvp:i64, zsvp:i64, wsvp:i64,
+           dx:f64, dy:f64, dz:f64, dw:f64) -> f64 {
+    let mut hash = seed ^ (xsvp ^ ysvp) ^ (zsvp ^ wsvp);
+    hash *= HASH_MULTIPLIER;
+    hash ^= hash >> 57;
+    let g = &grads[(((hash & 0x7FC) >> 2) as uint) % 160];
+    (g[0] * dx + g[1] * dy) + (g[2] * dz + g[3] * dw)
+}
+
+/// Floor truncated to an integer.
+fn fast_floor(x:f64) -> i32 {
+    let xi = x as i32;
+    if x < xi as f64 { xi - 1 } else { xi }
+}
+
+/// Round truncated to an integer, halfway cases away from zero.
+fn fast_round(x:f64) -> i32 {
+    if x < 0.0 { (x - 0.5) as i32 } else { (x + 0.5) as i32 }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::{Noise, Noise4D};
+    use super::{OpenSimplex2, OpenSimplex2S};
+
+    static POINTS: [[f32, ..4], ..5] = [
+        [0.5, 0.25, 0.75, 0.125],
+        [-1.3, 2.7, -0.4, 3.3],
+        [12.34, -56.78, 9.1, -0.6],
+        [-0.1, -0.2, -0.3, -0.4],
+        [1000.5, -2000.25, 300.125, 40.0625],
+    ];
+
+    /// Values at `POINTS` of a double precision transcription of the
+    /// reference implementation, for the seeds 0 and 42: 2D, 3D,
+    /// 3D favoring xy, then 4D.
+    static REFERENCE: [[[f32, ..4], ..5], ..2] = [
+        [
+            [0.4511446, 0.01773338, 0.28380865, 0.086965635],
+            [-0.32645634, 0.16715534, 0.13169529, 0.16131626],
+            [0.43640265, 0.43594086, 0.7070706, -0.8234452],
+            [-0.9129438, -0.56858635, -0.6499861, 0.09691566],
+            [0.55287063, -0.12114957, 0.18789351, 0.22978805],
+        ],
+        [
+            [0.2617592, 0.07512513, 0.3045677, 0.26647982],
+            [0.77874553, -0.12696944, 0.77152205, 0.3370543],
+            [-0.2523713, -0.4334183, 0.24241258, 0.42492145],
+            [0.40390304, 0.38822272, 0.16043349, 0.333749],
+            [0.44689715, 0.19183092, 0.10786853, 0.22210929],
+        ],
+    ];
+
+    /// Same as `REFERENCE`, for OpenSimplex2S.
+    static REFERENCE_SMOOTH: [[[f32, ..4], ..5], ..2] = [
+        [
+            [0.33292857, 0.20986663, 0.61208224, -0.226953],
+            [-0.24251676, 0.11941069, 0.080972716, 0.09569531],
+            [0.25735217, 0.5116568, 0.51461136, -0.32478625],
+            [-0.5703902, -0.50105184, -0.52292866, 0.333084],
+            [0.6002206, -0.09486855, -0.2016293, -0.39046764],
+        ],
+        [
+            [0.14751197, 0.6109822, 0.6731579, -0.03478735],
+            [0.4595084, -0.09184827, 0.57032627, 0.35593084],
+            [-0.14572355, -0.57102686, 0.17565703, 0.56160516],
+            [0.24894547, 0.56506824, 0.16958144, -0.13926578],
+            [0.42068243, 0.2591002, 0.172956, -0.27236316],
+        ],
+    ];
+
+    static SEEDS: [u64, ..2] = [0, 42];
+
+    fn assert_close(value:f32, expected:f32) {
+        assert!((value - expected).abs() < 1e-6,
+                "expected {}, got {}", expected, value);
+    }
+
+    #[test]
+    fn fast_matches_the_reference() {
+        for (s, &seed) in SEEDS.iter().enumerate() {
+            let noise = OpenSimplex2::with_seed(seed);
+            for (p, point) in POINTS.iter().enumerate() {
+                let expected = REFERENCE[s][p];
+                assert_close(noise.get_2d(point[0], point[1]), expected[0]);
+                assert_close(noise.get_value(point[0], point[1], point[2]), expected[1]);
+                assert_close(noise.get_value_3d_xy(point[0], point[1], point[2]), expected[2]);
+                assert_close(noise.get_4d(point[0], point[1], point[2], point[3]), expected[3]);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_matches_the_reference() {
+        for (s, &seed) in SEEDS.iter().enumerate() {
+            let noise = OpenSimplex2S::with_seed(seed);
+            for (p, point) in POINTS.iter().enumerate() {
+                let expected = REFERENCE_SMOOTH[s][p];
+                assert_close(noise.get_2d(point[0], point[1]), expected[0]);
+                assert_close(noise.get_value(point[0], point[1], point[2]), expected[1]);
+                assert_close(noise.get_value_3d_xy(point[0], point[1], point[2]), expected[2]);
+                assert_close(noise.get_4d(point[0], point[1], point[2], point[3]), expected[3]);
+            }
+        }
+    }
+
+    #[test]
+    fn values_are_in_range() {
+        let fast = OpenSimplex2::with_seed(7);
+        let smooth = OpenSimplex2S::with_seed(7);
+        for i in range(0u, 2000) {
+            let t = i as f32 * 0.37;
+            let (x, y, z, w) = (t, t * -0.71 + 3.0, t * 0.13 - 5.0, t * 1.9);
+            let values = [fast.get_2d(x, y), fast.get_value(x, y, z), fast.get_4d(x, y, z, w),
+                          smooth.get_2d(x, y), smooth.get_value(x, y, z),
+                          smooth.get_4d(x, y, z, w)];
+            for &v in values.iter() {
+                assert!(v >= -1.0 && v <= 1.0, "{} out of [-1, 1]", v);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -1,84 +1,119 @@
-use noise::Noise;
-
-/// A pre-caclulated permutation of 256.
-static P: [uint, ..256] = [
-    151,  160,  137,  91,   90,   15,   131,  13,   201,  95,
-    96,   53,   194,  233,  7,    225,  140,  36,   103,  30,
-    69,   142,  8,    99,   37,   240,  21,   10,   23,   190,
-    6,    148,  247,  120,  234,  75,   0,    26,   197,  62, 
-    94,   252,  219,  203,  117,  35,   11,   32,   57,   177,
-    33,   88,   237,  149,  56,   87,   174,  20,   125,  136,
-    171,  168,  68,   175,  74,   165,  71,   134,  139,  48,
-    27,   166,  77,   146,  158,  231,  83,   111,  229,  122,
-    60,   211,  133,  230,  220,  105,  92,   41,   55,   46,
-    245,  40,   244,  102,  143,  54,   65,   25,   63,   161,
-    1,    216,  80,   73,   209,  76,   132,  187,  208,  89,
-    18,   169,  200,  196,  135,  130,  116,  188,  159,  86,
-    164,  100,  109,  198,  173,  186,  3,    64,   52,   217,
-    226,  250,  124,  123,  5,    202,  38,   147,  118,  126,
-    255,  82,   85,   212,  207,  206,  59,   227,  47,   16,
-    58,   17,   182,  189,  28,   42,   223,  183,  170,  213,
-    119,  248,  152,  2,    44,   154,  163,  70,   221,  153,
-    101,  155,  167,  43,   172,  9,    129,  22,   39,   253,
-    19,   98,   108,  110,  79,   113,  224,  232,  178,  185,
-    112,  104,  218,  246,  97,   228,  251,  34,   242,  193,
-    238,  210,  144,  12,   191,  179,  162,  241,  81,   51,
-    145,  235,  249,  14,   239,  107,  49,   192,  214,  31,
-    181,  199,  106,  157,  184,  84,   204,  176,  115,  121, 
-    50,   45,   127,  4,    150,  254,  138,  236,  205,  93,
-    222,  114,  67,   29,   24,   72,   243,  141,  128,  195,
-    78,   66,   215,  61,   156,  180,  
-];                                                                                                                        
-
-/// 3D Perlin noise generator.
+use math::{NoiseFloat, fade, fade_derivative, lerp};
+use noise::{BasisNoise, Noise, Noise4D, Noise4D64, Noise64, NoiseWithDerivative};
+use noise::{Raw, AmplitudeSum, Bounded, UnitRange, measure_range};
+use octaves::Octaves;
+use permutation::PermutationTable;
+
+/// Perlin noise generator, in 1, 2, 3 and 4 dimensions.
 pub struct Perlin {
-    /// Controls the amount of details.
-    octave_count: uint,
-    /// The frequency of the first octave.
-    frequency: f32,
-    /// The frequency multiplier between successive octaves.
-    lacuranity: f32,
-    /// Controls the roughness.
-    persistence: f32,
+    /// The octave parameters.
+    octaves: Octaves,
+    /// The seed used to shuffle the permutation table.
+    seed: u64,
+    /// Hashes the lattice coordinates.
+    permutation: PermutationTable,
 }
 
+impl_seed_setters!(Perlin)
+
+impl_octave_setters!(Perlin, persistence)
+
 impl Perlin {
     /// Creates a Perlin noise generator with default parameters.
+    /// It uses Ken Perlin's reference permutation.
     pub fn new() -> Perlin {
-        Perlin {
-            octave_count: 6,
-            frequency: 1.0,
-            persistence: 0.5,
-            lacuranity: 2.0,
-        }
+        let mut perlin = Perlin {
+            octaves: Octaves::new(),
+            seed: 0,
+            permutation: PermutationTable::reference(),
+        };
+        perlin.octaves.ranges = perlin.measure_ranges();
+        perlin
     }
 
-    /// Sets the number of octaves.
-    pub fn set_octave_count(&mut self, n:uint) {
-        self.octave_count = n;
+    /// Measures the range of a single octave in 1, 2, 3 and 4 dimensions.
+    fn measure_ranges(&self) -> [(f32, f32), ..4] {
+        [measure_range(|x, _, _, _| self.generate_noise_1d(x)),
+         measure_range(|x, y, _, _| self.generate_noise_2d(x, y)),
+         self.get_range(),
+         measure_range(|x, y, z, w| self.generate_noise_4d(x, y, z, w))]
     }
 
-    /// Sets the frequency of the first octave.
-    pub fn set_frequency(&mut self, frequency:f32) {
-        self.frequency = frequency;
+    /// Generate one point 1D noise for one octave.
+    /// Same as the one octave 3D noise at (x,0,0), from the 2 corners
+    /// of the segment.
+    fn generate_noise_1d<T: NoiseFloat>(&self, x:T) -> T {
+        let zero: T = NoiseFloat::from_f64(0.0);
+        let one: T = NoiseFloat::from_f64(1.0);
+
+        let int_x = (x.floor_int() & 0xFF) as uint;
+        let x = x - x.floor();
+        let u = fade(x);
+
+        let p = &self.permutation;
+        let aa = p.get(p.get(int_x));
+        let ba = p.get(p.get(int_x+1));
+
+        lerp(u, Perlin::grad(p.get(aa), x,     zero, zero),
+                Perlin::grad(p.get(ba), x-one, zero, zero))
     }
 
-    /// Sets the persistence of the signal over succesive octaves.
-    pub fn set_persistence(&mut self, persistence:f32) {
-        self.persistence = persistence;
+    /// Generate one point 2D noise for one octave.
+    /// Same as the one octave 3D noise at (x,y,0), from the 4 corners
+    /// of the square.
+    fn generate_noise_2d<T: NoiseFloat>(&self, x:T, y:T) -> T {
+        let zero: T = NoiseFloat::from_f64(0.0);
+        let one: T = NoiseFloat::from_f64(1.0);
+
+        let int_x = (x.floor_int() & 0xFF) as uint;
+        let int_y = (y.floor_int() & 0xFF) as uint;
+
+        let x = x - x.floor();
+        let y = y - y.floor();
+
+        let u = fade(x);
+        let v = fade(y);
+
+        let p = &self.permutation;
+        let a =   p.get(int_x)+int_y;
+        let aa =  p.get(a);
+        let ab =  p.get(a+1);
+        let b =   p.get(int_x+1)+int_y;
+        let ba =  p.get(b);
+        let bb =  p.get(b+1);
+
+        lerp(v,
+            lerp(u, Perlin::grad(p.get(aa), x,    y,    zero),
+                    Perlin::grad(p.get(ba), x-one,y,    zero)),
+            lerp(u, Perlin::grad(p.get(ab), x,    y-one,zero),
+                    Perlin::grad(p.get(bb), x-one,y-one,zero)))
     }
 
-    /// Set the frequency multiplier.
-    pub fn set_lacuranity(&mut self, lacuranity:f32) {
-        self.lacuranity = lacuranity;
+    /// Returns the hashes of the corners of the unit cube whose lowest
+    /// corner is the wrapped lattice point(int_x,int_y,int_z), in the
+    /// order 000, 100, 010, 110, 001, 101, 011, 111 of their offsets.
+    fn cube_hashes(&self, int_x:uint, int_y:uint, int_z:uint) -> [uint, ..8] {
+        let p = &self.permutation;
+        let a =   p.get(int_x)+int_y;
+        let aa =  p.get(a)+int_z;
+        let ab =  p.get(a+1)+int_z;
+        let b =   p.get(int_x+1)+int_y;
+        let ba =  p.get(b)+int_z;
+        let bb =  p.get(b+1)+int_z;
+        [p.get(aa),   p.get(ba),   p.get(ab),   p.get(bb),
+         p.get(aa+1), p.get(ba+1), p.get(ab+1), p.get(bb+1)]
     }
 
     /// Generate one point noise for one octave.
-    fn generate_noise(&self, x:f32, y:f32, z:f32) -> f32 {
+    fn generate_noise<T: NoiseFloat>(&self, x:T, y:T, z:T) -> T {
+        let one: T = NoiseFloat::from_f64(1.0);
+
         // Find integer position of the unit cube that contains point.
-        let int_x = x.floor() as uint;
-        let int_y = y.floor() as uint;
-        let int_z = z.floor() as uint;
+        // The floor is taken as a signed integer so that negative coordinates
+        // get their own cells, then wrapped into the permutation table.
+        let int_x = (x.floor_int() & 0xFF) as uint;
+        let int_y = (y.floor_int() & 0xFF) as uint;
+        let int_z = (z.floor_int() & 0xFF) as uint;
 
         // Move absolute position to cube relative position.
         let x = x - x.floor();
@@ -86,49 +121,188 @@ impl Perlin {
         let z = z - z.floor();
 
         // Compute S-curves for x, y and z.
-        let u = Perlin::fade(x);
-        let v = Perlin::fade(y);
-        let w = Perlin::fade(z);
-
-        // Find hash coordinates for cube corners. 
-        let a =   P[int_x     & 0xFF]+int_y;
-        let aa =  P[a         & 0xFF]+int_z;
-        let ab =  P[(a+1)     & 0xFF]+int_z;
-        let b =   P[(int_x+1) & 0xFF]+int_y;
-        let ba =  P[b         & 0xFF]+int_z;
-        let bb =  P[(b+1)     & 0xFF]+int_z;
+        let u = fade(x);
+        let v = fade(y);
+        let w = fade(z);
+
+        // Find hash coordinates for cube corners.
+        let h = self.cube_hashes(int_x, int_y, int_z);
 
         // Compute gradients and interpolate them in this factorized expression.
-        Perlin::lerp(w,
-            Perlin::lerp(v,
-                Perlin::lerp(u, Perlin::grad(P[aa & 0xFF],   x,    y,    z),
-                                Perlin::grad(P[ba & 0xFF],   x-1.0,y,    z)),
-                Perlin::lerp(u, Perlin::grad(P[ab & 0xFF],   x,    y-1.0,z),
-                                Perlin::grad(P[bb & 0xFF],   x-1.0,y-1.0,z))),
-            Perlin::lerp(v,
-                Perlin::lerp(u, Perlin::grad(P[(aa+1) & 0xFF], x,    y,    z-1.0),
-                                Perlin::grad(P[(ba+1) & 0xFF], x-1.0,y,    z-1.0)),
-                Perlin::lerp(u, Perlin::grad(P[(ab+1) & 0xFF], x,    y-1.0,z-1.0),
-                                Perlin::grad(P[(bb+1) & 0xFF], x-1.0,y-1.0,z-1.0))))
+        lerp(w,
+            lerp(v,
+                lerp(u, Perlin::grad(h[0], x,    y,    z),
+                        Perlin::grad(h[1], x-one,y,    z)),
+                lerp(u, Perlin::grad(h[2], x,    y-one,z),
+                        Perlin::grad(h[3], x-one,y-one,z))),
+            lerp(v,
+                lerp(u, Perlin::grad(h[4], x,    y,    z-one),
+                        Perlin::grad(h[5], x-one,y,    z-one)),
+                lerp(u, Perlin::grad(h[6], x,    y-one,z-one),
+                        Perlin::grad(h[7], x-one,y-one,z-one))))
     }
 
-    /// Compute S-curve.
-    fn fade(t:f32) -> f32 {
-        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    /// Generate one point noise and its derivatives for one octave.
+    /// The value is the same as `generate_noise`.
+    fn generate_noise_with_derivative<T: NoiseFloat>(&self, x:T, y:T, z:T) -> (T, [T, ..3]) {
+        let zero: T = NoiseFloat::from_f64(0.0);
+        let one: T = NoiseFloat::from_f64(1.0);
+
+        // Find integer position of the unit cube that contains point.
+        let int_x = (x.floor_int() & 0xFF) as uint;
+        let int_y = (y.floor_int() & 0xFF) as uint;
+        let int_z = (z.floor_int() & 0xFF) as uint;
+
+        // Move absolute position to cube relative position.
+        let x = x - x.floor();
+        let y = y - y.floor();
+        let z = z - z.floor();
+
+        // Compute S-curves for x, y and z, and their derivatives.
+        let u = fade(x);
+        let v = fade(y);
+        let w = fade(z);
+        let du = fade_derivative(x);
+        let dv = fade_derivative(y);
+        let dw = fade_derivative(z);
+
+        // Gradients of the corners, named after their offset.
+        let h = self.cube_hashes(int_x, int_y, int_z);
+        let g000: [T, ..3] = Perlin::grad_vector(h[0]);
+        let g100: [T, ..3] = Perlin::grad_vector(h[1]);
+        let g010: [T, ..3] = Perlin::grad_vector(h[2]);
+        let g110: [T, ..3] = Perlin::grad_vector(h[3]);
+        let g001: [T, ..3] = Perlin::grad_vector(h[4]);
+        let g101: [T, ..3] = Perlin::grad_vector(h[5]);
+        let g011: [T, ..3] = Perlin::grad_vector(h[6]);
+        let g111: [T, ..3] = Perlin::grad_vector(h[7]);
+
+        // Corner values, the same as `grad` gives.
+        let n000 = Perlin::grad(h[0], x,     y,     z);
+        let n100 = Perlin::grad(h[1], x-one, y,     z);
+        let n010 = Perlin::grad(h[2], x,     y-one, z);
+        let n110 = Perlin::grad(h[3], x-one, y-one, z);
+        let n001 = Perlin::grad(h[4], x,     y,     z-one);
+        let n101 = Perlin::grad(h[5], x-one, y,     z-one);
+        let n011 = Perlin::grad(h[6], x,     y-one, z-one);
+        let n111 = Perlin::grad(h[7], x-one, y-one, z-one);
+
+        let value = lerp(w,
+            lerp(v, lerp(u, n000, n100), lerp(u, n010, n110)),
+            lerp(v, lerp(u, n001, n101), lerp(u, n011, n111)));
+
+        // Coefficients of the interpolation expanded as a polynomial
+        // of (u,v,w): k0 + k1.u + k2.v + k3.w + k4.uv + k5.vw + k6.wu + k7.uvw.
+        let k1 = n100 - n000;
+        let k2 = n010 - n000;
+        let k3 = n001 - n000;
+        let k4 = n000 - n100 - n010 + n110;
+        let k5 = n000 - n010 - n001 + n011;
+        let k6 = n000 - n100 - n001 + n101;
+        let k7 = -n000 + n100 + n010 - n110 + n001 - n101 - n011 + n111;
+
+        // The derivative is the interpolated gradient, plus the variation
+        // of the interpolation weights.
+        let mut derivative = [zero, ..3];
+        for i in range(0u, 3) {
+            derivative[i] = lerp(w,
+                lerp(v, lerp(u, g000[i], g100[i]), lerp(u, g010[i], g110[i])),
+                lerp(v, lerp(u, g001[i], g101[i]), lerp(u, g011[i], g111[i])));
+        }
+        derivative[0] = derivative[0] + du * (k1 + k4 * v + k6 * w + k7 * v * w);
+        derivative[1] = derivative[1] + dv * (k2 + k5 * w + k4 * u + k7 * w * u);
+        derivative[2] = derivative[2] + dw * (k3 + k6 * u + k5 * v + k7 * u * v);
+
+        (value, derivative)
     }
 
-    /// Linear interpolation.
-    fn lerp(t:f32, a:f32, b:f32) -> f32 {
-        a + t * (b - a)
+    /// Generate one point 4D noise for one octave.
+    fn generate_noise_4d<T: NoiseFloat>(&self, x:T, y:T, z:T, w:T) -> T {
+        let zero: T = NoiseFloat::from_f64(0.0);
+
+        // Find integer position of the unit tesseract that contains point.
+        let int_x = (x.floor_int() & 0xFF) as uint;
+        let int_y = (y.floor_int() & 0xFF) as uint;
+        let int_z = (z.floor_int() & 0xFF) as uint;
+        let int_w = (w.floor_int() & 0xFF) as uint;
+
+        // Move absolute position to tesseract relative position.
+        let x = x - x.floor();
+        let y = y - y.floor();
+        let z = z - z.floor();
+        let w = w - w.floor();
+
+        // Compute S-curves for x, y, z and w.
+        let u = fade(x);
+        let v = fade(y);
+        let s = fade(z);
+        let t = fade(w);
+
+        // Gradient of the corner at the given offset from the base corner.
+        let p = &self.permutation;
+        let corner = |i: uint, j: uint, k: uint, l: uint| -> T {
+            let hash = p.get(int_x+i + p.get(int_y+j + p.get(int_z+k + p.get(int_w+l))));
+            let c = |v: uint| -> T { NoiseFloat::from_f64(v as f64) };
+            Perlin::grad_4d(hash, x - c(i), y - c(j), z - c(k), w - c(l))
+        };
+
+        // Interpolate each of the two cubes of the tesseract along w.
+        let mut cubes = [zero, ..2];
+        for l in range(0u, 2) {
+            cubes[l] = lerp(s,
+                lerp(v,
+                    lerp(u, corner(0, 0, 0, l), corner(1, 0, 0, l)),
+                    lerp(u, corner(0, 1, 0, l), corner(1, 1, 0, l))),
+                lerp(v,
+                    lerp(u, corner(0, 0, 1, l), corner(1, 0, 1, l)),
+                    lerp(u, corner(0, 1, 1, l), corner(1, 1, 1, l))));
+        }
+
+        // The gradients have three non-zero components instead of two,
+        // scale the result back to the range of the 3D noise.
+        lerp(t, cubes[0], cubes[1]) * NoiseFloat::from_f64(2.0 / 3.0)
     }
 
     /// Compute gradient from hash and coordinates.
-    fn grad(hash:uint, x:f32, y:f32, z:f32) -> f32 {
+    fn grad<T: NoiseFloat>(hash:uint, x:T, y:T, z:T) -> T {
         let h = hash & 15;
         let u = if h < 8 { x } else { y };
         let v = if h < 4 { y } else if h == 12 || h == 14 { x } else { z };
         ( if h&1 == 0 { u } else { -u } ) + ( if h&2 == 0 { v } else { -v } )
     }
+
+    /// Returns the gradient vector used by `grad` for the hash.
+    fn grad_vector<T: NoiseFloat>(hash:uint) -> [T, ..3] {
+        let zero: T = NoiseFloat::from_f64(0.0);
+        let one: T = NoiseFloat::from_f64(1.0);
+        let h = hash & 15;
+        let mut g = [zero, ..3];
+        let u = if h < 8 { 0u } else { 1u };
+        let v = if h < 4 { 1u } else if h == 12 || h == 14 { 0u } else { 2u };
+        g[u] = if h&1 == 0 { one } else { -one };
+        g[v] = if h&2 == 0 { one } else { -one };
+        g
+    }
+
+    /// Compute 4D gradient from hash and coordinates.
+    /// The 32 gradients are the middles of the edges of a tesseract.
+    fn grad_4d<T: NoiseFloat>(hash:uint, x:T, y:T, z:T, w:T) -> T {
+        let h = hash & 31;
+        let (a, b, c) = match h >> 3 {
+            0 => (y, z, w),
+            1 => (x, z, w),
+            2 => (x, y, w),
+            _ => (x, y, z),
+        };
+        ( if h&4 == 0 { a } else { -a } ) + ( if h&2 == 0 { b } else { -b } ) + ( if h&1 == 0 { c } else { -c } )
+    }
+}
+
+impl BasisNoise for Perlin {
+    /// Returns the noise value of a single octave at the point(x,y,z).
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.generate_noise(x, y, z)
+    }
 }
 
 /// Implements the noise generator common trait.
@@ -136,40 +310,207 @@ impl Noise for Perlin {
     /// Returns the noise value at the point(x,y,z)
     /// generated with the current parameters.
     fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
-        // Mutability
-        let mut x = x;
-        let mut y = y;
-        let mut z = z;
-
-        // The computed noise value.
-        let mut value = 0.0;
-        // The current persistence to decrease between each octave.
-        let mut cur_persistence = 1.0;
-        // The total amplitude is used to normalize the final value.
-        let mut total_amplitude = 0.0;
-
-        // Apply the frequency.
-        x *= self.frequency;
-        y *= self.frequency;
-        z *= self.frequency;
-
-        // For each octave.
-        for _ in range(1, self.octave_count + 1) {
-            // Compute the noise value.
-            value += self.generate_noise(x, y, z) * cur_persistence;
-            // Then prepare the next octave.
-            x *= self.lacuranity;
-            y *= self.lacuranity;
-            z *= self.lacuranity;
-            total_amplitude += cur_persistence;
-            cur_persistence *= self.persistence;
-        }
-        // Normalize if necessary.
-        if value.abs() > 1.0 {
-            value /= total_amplitude;
+        let value = self.octaves.sum_3d(x, y, z, |x, y, z| self.generate_noise(x, y, z));
+        // The bounded normalizations never leave [-1, 1].
+        match self.octaves.normalization {
+            Bounded | UnitRange => debug_assert!(value.abs() <= 1.0),
+            Raw | AmplitudeSum => {}
         }
-        debug_assert!(value.abs() <= 1.0);
-        // The value can be returned here.
         value
     }
+
+    /// Returns the noise value at the point(x)
+    /// generated with the current parameters.
+    /// With a single octave, same as `get_value(x, 0.0, 0.0)`.
+    fn get_1d(&self, x:f32) -> f32 {
+        self.octaves.sum_1d(x, |x| self.generate_noise_1d(x))
+    }
+
+    /// Returns the noise value at the point(x,y)
+    /// generated with the current parameters.
+    /// With a single octave, same as `get_value(x, y, 0.0)`.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.octaves.sum_2d(x, y, |x, y| self.generate_noise_2d(x, y))
+    }
+}
+
+impl Noise4D for Perlin {
+    /// Returns the noise value at the point(x,y,z,w)
+    /// generated with the current parameters.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.octaves.sum_4d(x, y, z, w, |x, y, z, w| self.generate_noise_4d(x, y, z, w))
+    }
+}
+
+impl Noise64 for Perlin {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters, in double precision.
+    fn get_value_f64(&self, x:f64, y:f64, z:f64) -> f64 {
+        self.octaves.sum_3d(x, y, z, |x, y, z| self.generate_noise(x, y, z))
+    }
+
+    /// Returns the noise value at the point(x)
+    /// generated with the current parameters, in double precision.
+    fn get_1d_f64(&self, x:f64) -> f64 {
+        self.octaves.sum_1d(x, |x| self.generate_noise_1d(x))
+    }
+
+    /// Returns the noise value at the point(x,y)
+    /// generated with the current parameters, in double precision.
+    fn get_2d_f64(&self, x:f64, y:f64) -> f64 {
+        self.octaves.sum_2d(x, y, |x, y| self.generate_noise_2d(x, y))
+    }
+}
+
+impl Noise4D64 for Perlin {
+    /// Returns the noise value at the point(x,y,z,w)
+    /// generated with the current parameters, in double precision.
+    fn get_4d_f64(&self, x:f64, y:f64, z:f64, w:f64) -> f64 {
+        self.octaves.sum_4d(x, y, z, w, |x, y, z, w| self.generate_noise_4d(x, y, z, w))
+    }
+}
+
+impl NoiseWithDerivative for Perlin {
+    /// Returns the noise value at the point(x,y,z) generated with the
+    /// current parameters, and its partial derivatives along x, y and z.
+    fn get_value_with_derivative(&self, x:f32, y:f32, z:f32) -> (f32, [f32, ..3]) {
+        self.octaves.sum_3d_with_derivative(x, y, z, |x, y, z| {
+            self.generate_noise_with_derivative(x, y, z)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::{BasisNoise, Noise, Noise4D, Noise4D64, Noise64, NoiseWithDerivative};
+    use noise::{Raw, AmplitudeSum, Bounded, UnitRange};
+    use super::Perlin;
+
+    /// Points on both sides of the origin, and far from it.
+    static POINTS: [[f32, ..3], ..4] = [
+        [0.5, 0.25, 0.75],
+        [-1.3, 2.7, -0.4],
+        [12.34, -56.78, 9.1],
+        [-0.1, -0.2, -0.3],
+    ];
+
+    #[test]
+    fn same_seed_gives_same_values() {
+        let a = Perlin::with_seed(42);
+        let mut b = Perlin::new();
+        b.set_seed(42);
+        for p in POINTS.iter() {
+            assert_eq!(a.get_value(p[0], p[1], p[2]), b.get_value(p[0], p[1], p[2]));
+        }
+    }
+
+    #[test]
+    fn different_seeds_give_different_values() {
+        let a = Perlin::with_seed(42);
+        let b = Perlin::with_seed(7);
+        for p in POINTS.iter() {
+            assert!(a.get_value(p[0], p[1], p[2]) != b.get_value(p[0], p[1], p[2]));
+        }
+    }
+
+    #[test]
+    fn seeded_values_do_not_drift() {
+        // Single octave values, which only depend on the permutation.
+        let expected = [[0.32554483, -0.15398741],
+                        [-0.24646562, 0.11821619],
+                        [0.25483164, -0.4995414],
+                        [0.042860776, 0.032543793]];
+        let a = Perlin::with_seed(42);
+        let b = Perlin::with_seed(7);
+        for (p, e) in POINTS.iter().zip(expected.iter()) {
+            assert!((a.get_basis(p[0], p[1], p[2]) - e[0]).abs() < 1e-6);
+            assert!((b.get_basis(p[0], p[1], p[2]) - e[1]).abs() < 1e-6);
+        }
+    }
+
+    /// Checks that `f` has no jump at `t`.
+    fn assert_continuous_at(t:f32, f: |f32| -> f32) {
+        let epsilon = 1e-4;
+        let (below, at, above) = (f(t - epsilon), f(t), f(t + epsilon));
+        assert!((at - below).abs() < 0.01 && (above - at).abs() < 0.01,
+                "jump at {}: {} {} {}", t, below, at, above);
+    }
+
+    #[test]
+    fn continuous_across_the_origin() {
+        let perlin = Perlin::with_seed(42);
+        // The origin, its neighbour cells and negative cells wrapping
+        // around the permutation table.
+        let cells = [0.0f32, 1.0, -1.0, -2.0, -3.0, -255.0, -256.0, -257.0];
+        for &t in cells.iter() {
+            for axis in range(0u, 3) {
+                assert_continuous_at(t, |t| {
+                    let mut p = [0.3f32, 0.6, 0.45];
+                    p[axis] = t;
+                    perlin.get_value(p[0], p[1], p[2])
+                });
+            }
+            assert_continuous_at(t, |t| perlin.get_1d(t));
+            assert_continuous_at(t, |t| perlin.get_2d(t, 0.6));
+            assert_continuous_at(t, |t| perlin.get_2d(0.3, t));
+        }
+    }
+
+    #[test]
+    fn single_and_double_precision_agree() {
+        let perlin = Perlin::with_seed(42);
+        let close = |a:f32, b:f64| assert!((a as f64 - b).abs() < 1e-5, "{} != {}", a, b);
+        for p in POINTS.iter() {
+            let (x, y, z, w) = (p[0], p[1], p[2], p[0] - p[1]);
+            let (xd, yd, zd, wd) = (x as f64, y as f64, z as f64, w as f64);
+            close(perlin.get_1d(x), perlin.get_1d_f64(xd));
+            close(perlin.get_2d(x, y), perlin.get_2d_f64(xd, yd));
+            close(perlin.get_value(x, y, z), perlin.get_value_f64(xd, yd, zd));
+            close(perlin.get_4d(x, y, z, w), perlin.get_4d_f64(xd, yd, zd, wd));
+        }
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        // The differences are taken in double precision, with a step small
+        // enough for the highest octave.
+        let h = 1e-4f64;
+        for &normalization in [Raw, AmplitudeSum, Bounded, UnitRange].iter() {
+            let mut perlin = Perlin::with_seed(42);
+            perlin.set_octave_count(4);
+            perlin.set_frequency(1.3);
+            perlin.set_octave_rotation(true);
+            perlin.set_normalization(normalization);
+            for i in range(0u, 40) {
+                let p = [i as f32 * 0.731 - 14.2, 3.9 - i as f32 * 0.377, i as f32 * 0.113 + 0.05];
+                let (value, d) = perlin.get_value_with_derivative(p[0], p[1], p[2]);
+                assert!((value - perlin.get_value(p[0], p[1], p[2])).abs() < 1e-5);
+                for axis in range(0u, 3) {
+                    let mut above = [p[0] as f64, p[1] as f64, p[2] as f64];
+                    let mut below = above;
+                    above[axis] += h;
+                    below[axis] -= h;
+                    let difference = (perlin.get_value_f64(above[0], above[1], above[2])
+                                      - perlin.get_value_f64(below[0], below[1], below[2])) / (2.0 * h);
+                    assert!((d[axis] as f64 - difference).abs() < 1e-3 * (1.0 + difference.abs()),
+                            "({}, {}, {}) along {}: {} != {}",
+                            p[0], p[1], p[2], axis, d[axis], difference);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeding_measures_the_ranges_again() {
+        let mut perlin = Perlin::new();
+        let before = perlin.octaves.ranges;
+        perlin.set_seed(7);
+        let measured = perlin.measure_ranges();
+        let mut changed = false;
+        for i in range(0u, 4) {
+            assert!(perlin.octaves.ranges[i] == measured[i]);
+            changed = changed || perlin.octaves.ranges[i] != before[i];
+        }
+        assert!(changed);
+    }
 }
\ No newline at end of file
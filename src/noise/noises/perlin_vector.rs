@@ -0,0 +1,93 @@
+use noise::{Noise, VectorNoise};
+use noises::perlin::Perlin;
+
+/// Vector valued Perlin noise generator.
+///
+/// Each component is read from its own Perlin noise, seeded differently,
+/// so that the components are uncorrelated. Suitable as a displacement
+/// for domain warping, or as a wind field.
+pub struct PerlinVector {
+    /// The noises of the x, y and z components.
+    x_component: Perlin,
+    y_component: Perlin,
+    z_component: Perlin,
+    /// The seed of the x component, the y and z ones follow it.
+    seed: u64,
+}
+
+impl PerlinVector {
+    /// Creates a vector Perlin noise generator with default parameters.
+    pub fn new() -> PerlinVector {
+        PerlinVector::with_seed(0)
+    }
+
+    /// Creates a vector Perlin noise generator with default parameters
+    /// and components seeded from `seed`.
+    pub fn with_seed(seed:u64) -> PerlinVector {
+        let mut vector = PerlinVector {
+            x_component: Perlin::new(),
+            y_component: Perlin::new(),
+            z_component: Perlin::new(),
+            seed: seed,
+        };
+        vector.set_seed(seed);
+        vector
+    }
+
+    /// Sets the seed of the components.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed;
+        self.x_component.set_seed(seed);
+        self.y_component.set_seed(seed + 1);
+        self.z_component.set_seed(seed + 2);
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the number of octaves.
+    pub fn set_octave_count(&mut self, n:uint) {
+        self.x_component.set_octave_count(n);
+        self.y_component.set_octave_count(n);
+        self.z_component.set_octave_count(n);
+    }
+
+    /// Sets the frequency of the first octave.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.x_component.set_frequency(frequency);
+        self.y_component.set_frequency(frequency);
+        self.z_component.set_frequency(frequency);
+    }
+
+    /// Sets the persistence of the signal over succesive octaves.
+    pub fn set_persistence(&mut self, persistence:f32) {
+        self.x_component.set_persistence(persistence);
+        self.y_component.set_persistence(persistence);
+        self.z_component.set_persistence(persistence);
+    }
+
+    /// Set the frequency multiplier.
+    pub fn set_lacuranity(&mut self, lacuranity:f32) {
+        self.x_component.set_lacuranity(lacuranity);
+        self.y_component.set_lacuranity(lacuranity);
+        self.z_component.set_lacuranity(lacuranity);
+    }
+}
+
+/// Implements the vector noise generator common trait.
+impl VectorNoise for PerlinVector {
+    /// Returns the vector at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        [self.x_component.get_value(x, y, z),
+         self.y_component.get_value(x, y, z),
+         self.z_component.get_value(x, y, z)]
+    }
+
+    /// Returns the vector at the point(x,y).
+    fn get_vector_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        [self.x_component.get_2d(x, y),
+         self.y_component.get_2d(x, y)]
+    }
+}
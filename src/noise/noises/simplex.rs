@@ -0,0 +1,403 @@
+use noise::{BasisNoise, Noise, Noise4D, measure_range};
+use octaves::Octaves;
+use permutation::PermutationTable;
+
+/// Skewing factor from 2D space to the simplex grid: (sqrt(3) - 1) / 2.
+static F2: f32 = 0.366025403784;
+/// Unskewing factor from the 2D simplex grid to space: (3 - sqrt(3)) / 6.
+static G2: f32 = 0.211324865405;
+/// Skewing factor from 3D space to the simplex grid.
+static F3: f32 = 1.0 / 3.0;
+/// Unskewing factor from the 3D simplex grid to space.
+static G3: f32 = 1.0 / 6.0;
+/// Skewing factor from 4D space to the simplex grid: (sqrt(5) - 1) / 4.
+static F4: f32 = 0.309016994375;
+/// Unskewing factor from the 4D simplex grid to space: (5 - sqrt(5)) / 20.
+static G4: f32 = 0.138196601125;
+
+/// Gradients for 2D and 3D noise: the middles of the edges of a cube.
+static GRAD3: [[f32, ..3], ..12] = [
+    [1.0, 1.0, 0.0], [-1.0, 1.0, 0.0], [1.0, -1.0, 0.0], [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [1.0, 0.0, -1.0], [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0], [0.0, -1.0, 1.0], [0.0, 1.0, -1.0], [0.0, -1.0, -1.0],
+];
+
+/// Gradients for 4D noise: the middles of the edges of a tesseract.
+static GRAD4: [[f32, ..4], ..32] = [
+    [0.0, 1.0, 1.0, 1.0],  [0.0, 1.0, 1.0, -1.0],  [0.0, 1.0, -1.0, 1.0],  [0.0, 1.0, -1.0, -1.0],
+    [0.0, -1.0, 1.0, 1.0], [0.0, -1.0, 1.0, -1.0], [0.0, -1.0, -1.0, 1.0], [0.0, -1.0, -1.0, -1.0],
+    [1.0, 0.0, 1.0, 1.0],  [1.0, 0.0, 1.0, -1.0],  [1.0, 0.0, -1.0, 1.0],  [1.0, 0.0, -1.0, -1.0],
+    [-1.0, 0.0, 1.0, 1.0], [-1.0, 0.0, 1.0, -1.0], [-1.0, 0.0, -1.0, 1.0], [-1.0, 0.0, -1.0, -1.0],
+    [1.0, 1.0, 0.0, 1.0],  [1.0, 1.0, 0.0, -1.0],  [1.0, -1.0, 0.0, 1.0],  [1.0, -1.0, 0.0, -1.0],
+    [-1.0, 1.0, 0.0, 1.0], [-1.0, 1.0, 0.0, -1.0], [-1.0, -1.0, 0.0, 1.0], [-1.0, -1.0, 0.0, -1.0],
+    [1.0, 1.0, 1.0, 0.0],  [1.0, 1.0, -1.0, 0.0],  [1.0, -1.0, 1.0, 0.0],  [1.0, -1.0, -1.0, 0.0],
+    [-1.0, 1.0, 1.0, 0.0], [-1.0, 1.0, -1.0, 0.0], [-1.0, -1.0, 1.0, 0.0], [-1.0, -1.0, -1.0, 0.0],
+];
+
+/// Simplex noise generator, in 2, 3 and 4 dimensions.
+///
+/// Each octave only sums the contributions of the corners of the simplex
+/// containing the point (3 in 2D, 4 in 3D, 5 in 4D) instead of the corners
+/// of a hypercube, and has no axis-aligned artifacts.
+pub struct Simplex {
+    /// The octave parameters.
+    octaves: Octaves,
+    /// The seed used to shuffle the permutation table.
+    seed: u64,
+    /// Hashes the lattice coordinates.
+    permutation: PermutationTable,
+}
+
+impl_seed_setters!(Simplex)
+
+impl_octave_setters!(Simplex, persistence)
+
+impl Simplex {
+    /// Creates a simplex noise generator with default parameters.
+    /// It uses Ken Perlin's reference permutation.
+    pub fn new() -> Simplex {
+        let mut simplex = Simplex {
+            octaves: Octaves::new(),
+            seed: 0,
+            permutation: PermutationTable::reference(),
+        };
+        simplex.octaves.ranges = simplex.measure_ranges();
+        simplex
+    }
+
+    /// Measures the range of a single octave in 2, 3 and 4 dimensions.
+    /// The 1D noise is a slice of the 3D one, and shares its range.
+    fn measure_ranges(&self) -> [(f32, f32), ..4] {
+        let range_3d = self.get_range();
+        [range_3d,
+         measure_range(|x, y, _, _| self.generate_noise_2d(x, y)),
+         range_3d,
+         measure_range(|x, y, z, w| self.generate_noise_4d(x, y, z, w))]
+    }
+
+    /// Generate one point 2D noise for one octave.
+    fn generate_noise_2d(&self, x:f32, y:f32) -> f32 {
+        // Skew the input space to find the simplex cell containing the point.
+        let s = (x + y) * F2;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+
+        // Unskew the cell origin back to (x,y) space
+        // and find the distances from it.
+        let t = (i + j) * G2;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+
+        // Find which of the two triangles of the cell contains the point.
+        let (i1, j1) = if x0 > y0 { (1u, 0u) } else { (0u, 1u) };
+
+        // Offsets of the middle and last corners.
+        let x1 = x0 - i1 as f32 + G2;
+        let y1 = y0 - j1 as f32 + G2;
+        let x2 = x0 - 1.0 + 2.0 * G2;
+        let y2 = y0 - 1.0 + 2.0 * G2;
+
+        // Hash the corners into gradient indices.
+        let p = &self.permutation;
+        let ii = ((i as int) & 0xFF) as uint;
+        let jj = ((j as int) & 0xFF) as uint;
+        let gi0 = p.get(ii      + p.get(jj))      % 12;
+        let gi1 = p.get(ii + i1 + p.get(jj + j1)) % 12;
+        let gi2 = p.get(ii + 1  + p.get(jj + 1))  % 12;
+
+        // Sum the corners contributions, scaled to stay in [-1, 1].
+        70.0 * (Simplex::corner_2d(gi0, x0, y0) +
+                Simplex::corner_2d(gi1, x1, y1) +
+                Simplex::corner_2d(gi2, x2, y2))
+    }
+
+    /// Generate one point 3D noise for one octave.
+    fn generate_noise_3d(&self, x:f32, y:f32, z:f32) -> f32 {
+        // Skew the input space to find the simplex cell containing the point.
+        let s = (x + y + z) * F3;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let k = (z + s).floor();
+
+        // Unskew the cell origin back to (x,y,z) space
+        // and find the distances from it.
+        let t = (i + j + k) * G3;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+
+        // Find which of the six tetrahedra of the cell contains the point.
+        let (i1, j1, k1, i2, j2, k2) =
+            if x0 >= y0 {
+                if y0 >= z0      { (1u, 0u, 0u, 1u, 1u, 0u) }
+                else if x0 >= z0 { (1u, 0u, 0u, 1u, 0u, 1u) }
+                else             { (0u, 0u, 1u, 1u, 0u, 1u) }
+            } else {
+                if y0 < z0       { (0u, 0u, 1u, 0u, 1u, 1u) }
+                else if x0 < z0  { (0u, 1u, 0u, 0u, 1u, 1u) }
+                else             { (0u, 1u, 0u, 1u, 1u, 0u) }
+            };
+
+        // Offsets of the second, third and last corners.
+        let x1 = x0 - i1 as f32 + G3;
+        let y1 = y0 - j1 as f32 + G3;
+        let z1 = z0 - k1 as f32 + G3;
+        let x2 = x0 - i2 as f32 + 2.0 * G3;
+        let y2 = y0 - j2 as f32 + 2.0 * G3;
+        let z2 = z0 - k2 as f32 + 2.0 * G3;
+        let x3 = x0 - 1.0 + 3.0 * G3;
+        let y3 = y0 - 1.0 + 3.0 * G3;
+        let z3 = z0 - 1.0 + 3.0 * G3;
+
+        // Hash the corners into gradient indices.
+        let p = &self.permutation;
+        let ii = ((i as int) & 0xFF) as uint;
+        let jj = ((j as int) & 0xFF) as uint;
+        let kk = ((k as int) & 0xFF) as uint;
+        let gi0 = p.get(ii      + p.get(jj      + p.get(kk)))      % 12;
+        let gi1 = p.get(ii + i1 + p.get(jj + j1 + p.get(kk + k1))) % 12;
+        let gi2 = p.get(ii + i2 + p.get(jj + j2 + p.get(kk + k2))) % 12;
+        let gi3 = p.get(ii + 1  + p.get(jj + 1  + p.get(kk + 1)))  % 12;
+
+        // Sum the corners contributions, scaled to stay in [-1, 1].
+        32.0 * (Simplex::corner_3d(gi0, x0, y0, z0) +
+                Simplex::corner_3d(gi1, x1, y1, z1) +
+                Simplex::corner_3d(gi2, x2, y2, z2) +
+                Simplex::corner_3d(gi3, x3, y3, z3))
+    }
+
+    /// Generate one point 4D noise for one octave.
+    fn generate_noise_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        // Skew the input space to find the simplex cell containing the point.
+        let s = (x + y + z + w) * F4;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let k = (z + s).floor();
+        let l = (w + s).floor();
+
+        // Unskew the cell origin back to (x,y,z,w) space
+        // and find the distances from it.
+        let t = (i + j + k + l) * G4;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+        let w0 = w - (l - t);
+
+        // Rank the coordinates to find which of the 24 simplices of the
+        // cell contains the point.
+        let mut rank_x = 0u;
+        let mut rank_y = 0u;
+        let mut rank_z = 0u;
+        let mut rank_w = 0u;
+        if x0 > y0 { rank_x += 1; } else { rank_y += 1; }
+        if x0 > z0 { rank_x += 1; } else { rank_z += 1; }
+        if x0 > w0 { rank_x += 1; } else { rank_w += 1; }
+        if y0 > z0 { rank_y += 1; } else { rank_z += 1; }
+        if y0 > w0 { rank_y += 1; } else { rank_w += 1; }
+        if z0 > w0 { rank_z += 1; } else { rank_w += 1; }
+
+        // The largest coordinates are stepped first.
+        let step = |rank: uint, threshold: uint| if rank >= threshold { 1u } else { 0u };
+        let (i1, j1, k1, l1) = (step(rank_x, 3), step(rank_y, 3), step(rank_z, 3), step(rank_w, 3));
+        let (i2, j2, k2, l2) = (step(rank_x, 2), step(rank_y, 2), step(rank_z, 2), step(rank_w, 2));
+        let (i3, j3, k3, l3) = (step(rank_x, 1), step(rank_y, 1), step(rank_z, 1), step(rank_w, 1));
+
+        // Offsets of the second, third, fourth and last corners.
+        let x1 = x0 - i1 as f32 + G4;
+        let y1 = y0 - j1 as f32 + G4;
+        let z1 = z0 - k1 as f32 + G4;
+        let w1 = w0 - l1 as f32 + G4;
+        let x2 = x0 - i2 as f32 + 2.0 * G4;
+        let y2 = y0 - j2 as f32 + 2.0 * G4;
+        let z2 = z0 - k2 as f32 + 2.0 * G4;
+        let w2 = w0 - l2 as f32 + 2.0 * G4;
+        let x3 = x0 - i3 as f32 + 3.0 * G4;
+        let y3 = y0 - j3 as f32 + 3.0 * G4;
+        let z3 = z0 - k3 as f32 + 3.0 * G4;
+        let w3 = w0 - l3 as f32 + 3.0 * G4;
+        let x4 = x0 - 1.0 + 4.0 * G4;
+        let y4 = y0 - 1.0 + 4.0 * G4;
+        let z4 = z0 - 1.0 + 4.0 * G4;
+        let w4 = w0 - 1.0 + 4.0 * G4;
+
+        // Hash the corners into gradient indices.
+        let p = &self.permutation;
+        let ii = ((i as int) & 0xFF) as uint;
+        let jj = ((j as int) & 0xFF) as uint;
+        let kk = ((k as int) & 0xFF) as uint;
+        let ll = ((l as int) & 0xFF) as uint;
+        let gi0 = p.get(ii      + p.get(jj      + p.get(kk      + p.get(ll))))      % 32;
+        let gi1 = p.get(ii + i1 + p.get(jj + j1 + p.get(kk + k1 + p.get(ll + l1)))) % 32;
+        let gi2 = p.get(ii + i2 + p.get(jj + j2 + p.get(kk + k2 + p.get(ll + l2)))) % 32;
+        let gi3 = p.get(ii + i3 + p.get(jj + j3 + p.get(kk + k3 + p.get(ll + l3)))) % 32;
+        let gi4 = p.get(ii + 1  + p.get(jj + 1  + p.get(kk + 1  + p.get(ll + 1))))  % 32;
+
+        // Sum the corners contributions, scaled to stay in [-1, 1].
+        27.0 * (Simplex::corner_4d(gi0, x0, y0, z0, w0) +
+                Simplex::corner_4d(gi1, x1, y1, z1, w1) +
+                Simplex::corner_4d(gi2, x2, y2, z2, w2) +
+                Simplex::corner_4d(gi3, x3, y3, z3, w3) +
+                Simplex::corner_4d(gi4, x4, y4, z4, w4))
+    }
+
+    /// Contribution of a 2D corner with gradient `gi` at offset (x,y).
+    fn corner_2d(gi:uint, x:f32, y:f32) -> f32 {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            0.0
+        } else {
+            let t = t * t;
+            let g = &GRAD3[gi];
+            t * t * (g[0] * x + g[1] * y)
+        }
+    }
+
+    /// Contribution of a 3D corner with gradient `gi` at offset (x,y,z).
+    fn corner_3d(gi:uint, x:f32, y:f32, z:f32) -> f32 {
+        let t = 0.6 - x * x - y * y - z * z;
+        if t < 0.0 {
+            0.0
+        } else {
+            let t = t * t;
+            let g = &GRAD3[gi];
+            t * t * (g[0] * x + g[1] * y + g[2] * z)
+        }
+    }
+
+    /// Contribution of a 4D corner with gradient `gi` at offset (x,y,z,w).
+    fn corner_4d(gi:uint, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let t = 0.6 - x * x - y * y - z * z - w * w;
+        if t < 0.0 {
+            0.0
+        } else {
+            let t = t * t;
+            let g = &GRAD4[gi];
+            t * t * (g[0] * x + g[1] * y + g[2] * z + g[3] * w)
+        }
+    }
+}
+
+impl BasisNoise for Simplex {
+    /// Returns the 3D noise value of a single octave at the point(x,y,z).
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.generate_noise_3d(x, y, z)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Simplex {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.octaves.sum_3d(x, y, z, |x, y, z| self.generate_noise_3d(x, y, z))
+    }
+
+    /// Returns the 2D simplex noise value at the point(x,y)
+    /// generated with the current parameters.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.octaves.sum_2d(x, y, |x, y| self.generate_noise_2d(x, y))
+    }
+}
+
+impl Noise4D for Simplex {
+    /// Returns the noise value at the point(x,y,z,w)
+    /// generated with the current parameters.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.octaves.sum_4d(x, y, z, w, |x, y, z, w| self.generate_noise_4d(x, y, z, w))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::{INFINITY, NEG_INFINITY};
+    use noise::{Noise, Noise4D};
+    use permutation::SplitMix64;
+    use super::Simplex;
+
+    /// Points on both sides of the origin, and far from it.
+    static POINTS: [[f32, ..4], ..4] = [
+        [0.5, 0.25, 0.75, 0.1],
+        [-1.3, 2.7, -0.4, 5.6],
+        [12.34, -56.78, 9.1, -3.3],
+        [-0.1, -0.2, -0.3, -0.4],
+    ];
+
+    #[test]
+    fn reference_values_do_not_drift() {
+        // Single octave values in 2D, 3D and 4D, with the reference permutation.
+        let expected = [[-0.6471489, 0.48243108, -0.036655232],
+                        [-0.41685644, 0.39659372, -0.36629346],
+                        [0.0658805, 0.57127565, -0.11728928],
+                        [0.285283, -0.58097404, -0.5514395]];
+        let simplex = Simplex::new();
+        for (p, e) in POINTS.iter().zip(expected.iter()) {
+            assert!((simplex.generate_noise_2d(p[0], p[1]) - e[0]).abs() < 1e-6);
+            assert!((simplex.generate_noise_3d(p[0], p[1], p[2]) - e[1]).abs() < 1e-6);
+            assert!((simplex.generate_noise_4d(p[0], p[1], p[2], p[3]) - e[2]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_values() {
+        let a = Simplex::with_seed(42);
+        let b = Simplex::with_seed(42);
+        let c = Simplex::with_seed(7);
+        for p in POINTS.iter() {
+            assert_eq!(a.get_2d(p[0], p[1]), b.get_2d(p[0], p[1]));
+            assert_eq!(a.get_value(p[0], p[1], p[2]), b.get_value(p[0], p[1], p[2]));
+            assert_eq!(a.get_4d(p[0], p[1], p[2], p[3]), b.get_4d(p[0], p[1], p[2], p[3]));
+            assert!(a.get_value(p[0], p[1], p[2]) != c.get_value(p[0], p[1], p[2]));
+        }
+    }
+
+    #[test]
+    fn single_octaves_stay_in_range() {
+        // The scale factors of the corner sums keep a single octave in
+        // [-1, 1], and it gets close to both ends.
+        let simplex = Simplex::new();
+        let mut rng = SplitMix64::new(3);
+        let mut lower = [INFINITY, ..3];
+        let mut upper = [NEG_INFINITY, ..3];
+        for _ in range(0u, 20000) {
+            let x = rng.next_f32() * 200.0 - 100.0;
+            let y = rng.next_f32() * 200.0 - 100.0;
+            let z = rng.next_f32() * 200.0 - 100.0;
+            let w = rng.next_f32() * 200.0 - 100.0;
+            let values = [simplex.generate_noise_2d(x, y),
+                          simplex.generate_noise_3d(x, y, z),
+                          simplex.generate_noise_4d(x, y, z, w)];
+            for i in range(0u, 3) {
+                lower[i] = lower[i].min(values[i]);
+                upper[i] = upper[i].max(values[i]);
+            }
+        }
+        for i in range(0u, 3) {
+            assert!(lower[i] >= -1.0 && lower[i] < -0.9, "{}D lower: {}", i + 2, lower[i]);
+            assert!(upper[i] <= 1.0 && upper[i] > 0.9, "{}D upper: {}", i + 2, upper[i]);
+        }
+    }
+
+    #[test]
+    fn continuous_across_the_cells() {
+        // Small steps along a line crossing many simplices, including
+        // negative ones.
+        let mut simplex = Simplex::with_seed(42);
+        simplex.set_octave_count(1);
+        let step = 1e-3f32;
+        let mut previous = [simplex.get_2d(-3.0, -1.7),
+                            simplex.get_value(-3.0, -1.7, -0.4),
+                            simplex.get_4d(-3.0, -1.7, -0.4, -2.2)];
+        for i in range(1u, 6000) {
+            let t = i as f32 * step;
+            let (x, y, z, w) = (-3.0 + t, -1.7 + 0.61 * t, -0.4 + 0.37 * t, -2.2 + 0.83 * t);
+            let values = [simplex.get_2d(x, y),
+                          simplex.get_value(x, y, z),
+                          simplex.get_4d(x, y, z, w)];
+            for d in range(0u, 3) {
+                assert!((values[d] - previous[d]).abs() < 0.05,
+                        "{}D jump at {}: {} {}", d + 2, t, previous[d], values[d]);
+            }
+            previous = values;
+        }
+    }
+}
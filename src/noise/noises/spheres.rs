@@ -0,0 +1,38 @@
+use noise::Noise;
+
+/// Concentric spheres generator.
+///
+/// The spheres are centered on the origin, one unit apart at a unit
+/// frequency. The output is 1 on their surface and falls to -1 halfway
+/// between two of them.
+pub struct Spheres {
+    /// The number of spheres per unit.
+    frequency: f32,
+}
+
+impl Spheres {
+    /// Creates a concentric spheres generator with a unit frequency.
+    pub fn new() -> Spheres {
+        Spheres { frequency: 1.0 }
+    }
+
+    /// Sets the number of spheres per unit.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.frequency = frequency;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Spheres {
+    /// Returns the noise value at the point(x,y,z).
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let x = x * self.frequency;
+        let y = y * self.frequency;
+        let z = z * self.frequency;
+        let distance = (x * x + y * y + z * z).sqrt();
+        // The distance to the nearest sphere, at most 0.5.
+        let from_smaller = distance - distance.floor();
+        let nearest = from_smaller.min(1.0 - from_smaller);
+        1.0 - nearest * 4.0
+    }
+}
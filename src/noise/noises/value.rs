@@ -0,0 +1,122 @@
+use math::{cubic_curve, fade, lerp};
+use noise::{BasisNoise, Noise};
+use octaves::Octaves;
+use permutation::PermutationTable;
+
+/// How the lattice values are interpolated inside a cell.
+pub enum Interpolation {
+    /// Straight interpolation, blocky with visible creases on cell borders.
+    Linear,
+    /// Cubic S-curve, continuous slope across cell borders.
+    Cubic,
+    /// Quintic S-curve, continuous slope and curvature across cell borders.
+    Quintic,
+}
+
+/// 3D value noise generator.
+///
+/// Each lattice point holds a random value, interpolated inside the cells.
+/// Cheaper than gradient noise, at the cost of a blockier look.
+pub struct Value {
+    /// The octave parameters.
+    octaves: Octaves,
+    /// Smooths the values between the lattice points.
+    interpolation: Interpolation,
+    /// The seed used to shuffle the permutation table.
+    seed: u64,
+    /// Hashes the lattice coordinates.
+    permutation: PermutationTable,
+}
+
+impl_seed_setters!(Value)
+
+impl_octave_setters!(Value, persistence)
+
+impl Value {
+    /// Creates a value noise generator with default parameters.
+    /// It uses Ken Perlin's reference permutation.
+    pub fn new() -> Value {
+        let mut value = Value {
+            octaves: Octaves::new(),
+            interpolation: Quintic,
+            seed: 0,
+            permutation: PermutationTable::reference(),
+        };
+        value.octaves.ranges = value.measure_ranges();
+        value
+    }
+
+    /// Measures the range of a single octave. The lower dimensions are
+    /// slices of the 3D noise, and share its range.
+    fn measure_ranges(&self) -> [(f32, f32), ..4] {
+        [self.get_range(), ..4]
+    }
+
+    /// Sets the interpolation between the lattice points.
+    pub fn set_interpolation(&mut self, interpolation:Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Generate one point noise for one octave.
+    fn generate_noise(&self, x:f32, y:f32, z:f32) -> f32 {
+        // Find integer position of the unit cube that contains point.
+        let int_x = ((x.floor() as int) & 0xFF) as uint;
+        let int_y = ((y.floor() as int) & 0xFF) as uint;
+        let int_z = ((z.floor() as int) & 0xFF) as uint;
+
+        // Move absolute position to cube relative position.
+        let x = x - x.floor();
+        let y = y - y.floor();
+        let z = z - z.floor();
+
+        // Compute the interpolation weights for x, y and z.
+        let (u, v, w) = match self.interpolation {
+            Linear => (x, y, z),
+            Cubic => (cubic_curve(x), cubic_curve(y), cubic_curve(z)),
+            Quintic => (fade(x), fade(y), fade(z)),
+        };
+
+        // Find hash coordinates for cube corners.
+        let p = &self.permutation;
+        let a =   p.get(int_x)+int_y;
+        let aa =  p.get(a)+int_z;
+        let ab =  p.get(a+1)+int_z;
+        let b =   p.get(int_x+1)+int_y;
+        let ba =  p.get(b)+int_z;
+        let bb =  p.get(b+1)+int_z;
+
+        // Interpolate the corner values.
+        lerp(w,
+            lerp(v,
+                lerp(u, Value::lattice_value(p.get(aa)),
+                        Value::lattice_value(p.get(ba))),
+                lerp(u, Value::lattice_value(p.get(ab)),
+                        Value::lattice_value(p.get(bb)))),
+            lerp(v,
+                lerp(u, Value::lattice_value(p.get(aa+1)),
+                        Value::lattice_value(p.get(ba+1))),
+                lerp(u, Value::lattice_value(p.get(ab+1)),
+                        Value::lattice_value(p.get(bb+1)))))
+    }
+
+    /// Maps a hash to a lattice value in [-1, 1].
+    fn lattice_value(hash:uint) -> f32 {
+        hash as f32 / 127.5 - 1.0
+    }
+}
+
+impl BasisNoise for Value {
+    /// Returns the noise value of a single octave at the point(x,y,z).
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.generate_noise(x, y, z)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Value {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.octaves.sum_3d(x, y, z, |x, y, z| self.generate_noise(x, y, z))
+    }
+}
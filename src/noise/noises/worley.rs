@@ -0,0 +1,292 @@
+use std::cmp::max;
+use std::f32::{INFINITY, NAN};
+
+use noise::{BasisNoise, Noise};
+use permutation::SplitMix64;
+
+/// Multipliers used to mix the cell coordinates into a seed.
+static CELL_X: u64 = 0x5205402B9270C86F;
+static CELL_Y: u64 = 0x598CD327003817B5;
+static CELL_Z: u64 = 0x5BCC226E9FA0BACB;
+
+/// How the distance between a point and a feature point is measured.
+pub enum DistanceFunction {
+    /// Straight line distance.
+    Euclidean,
+    /// Sum of the distances along each axis.
+    Manhattan,
+    /// Largest of the distances along each axis.
+    Chebyshev,
+    /// Generalized distance of order p: 1 is Manhattan, 2 is Euclidean.
+    /// The order must be positive, orders below 1 are slower to search.
+    Minkowski(f32),
+}
+
+/// What the generator returns from the closest feature points.
+pub enum ReturnType {
+    /// Distance to the closest feature point.
+    F1,
+    /// Distance to the second closest feature point.
+    F2,
+    /// Difference between the two closest distances, zero on cell borders.
+    F2MinusF1,
+    /// Product of the two closest distances.
+    F1TimesF2,
+    /// A random value in [-1, 1] constant over the cell of the closest
+    /// feature point.
+    CellValue,
+}
+
+/// 3D Worley (cellular) noise generator.
+///
+/// Space is divided in unit cubes, each holding one randomly placed
+/// feature point. Distances are expressed in cube units.
+pub struct Worley {
+    /// The frequency of the cells.
+    frequency: f32,
+    /// The seed used to place the feature points.
+    seed: u64,
+    /// Measures the distance to the feature points.
+    distance: DistanceFunction,
+    /// Selects the value returned from the closest feature points.
+    return_type: ReturnType,
+}
+
+impl Worley {
+    /// Creates a Worley noise generator returning the euclidean
+    /// distance to the closest feature point.
+    pub fn new() -> Worley {
+        Worley {
+            frequency: 1.0,
+            seed: 0,
+            distance: Euclidean,
+            return_type: F1,
+        }
+    }
+
+    /// Creates a Worley noise generator with default parameters
+    /// and feature points placed from `seed`.
+    pub fn with_seed(seed:u64) -> Worley {
+        let mut worley = Worley::new();
+        worley.set_seed(seed);
+        worley
+    }
+
+    /// Sets the seed.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed;
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the frequency of the cells.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.frequency = frequency;
+    }
+
+    /// Sets the distance function.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the order of a `Minkowski` distance is not positive.
+    pub fn set_distance_function(&mut self, distance:DistanceFunction) {
+        match distance {
+            Minkowski(p) => assert!(p > 0.0, "the Minkowski order must be positive"),
+            _ => {}
+        }
+        self.distance = distance;
+    }
+
+    /// Sets the value returned from the closest feature points.
+    pub fn set_return_type(&mut self, return_type:ReturnType) {
+        self.return_type = return_type;
+    }
+
+    /// Returns a generator seeded from the cell coordinates, used to draw
+    /// the feature point and the value of the cell.
+    fn cell_rng(&self, i:i64, j:i64, k:i64) -> SplitMix64 {
+        SplitMix64::new(self.seed ^ (i as u64 * CELL_X) ^ (j as u64 * CELL_Y) ^ (k as u64 * CELL_Z))
+    }
+
+    /// Measures the distance of the offset (x,y,z).
+    fn distance(&self, x:f32, y:f32, z:f32) -> f32 {
+        match self.distance {
+            Euclidean => (x * x + y * y + z * z).sqrt(),
+            Manhattan => x.abs() + y.abs() + z.abs(),
+            Chebyshev => x.abs().max(y.abs()).max(z.abs()),
+            Minkowski(p) => {
+                (x.abs().powf(p) + y.abs().powf(p) + z.abs().powf(p)).powf(1.0 / p)
+            }
+        }
+    }
+
+    /// Generate one point noise, with one cell per unit cube. NaN for
+    /// infinite or NaN coordinates.
+    fn generate_noise(&self, x:f32, y:f32, z:f32) -> f32 {
+        // The distances to such points are never finite, and the search
+        // below would never end.
+        if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+            return NAN;
+        }
+
+        // Find the cell containing the point.
+        let cell_x = x.floor() as i64;
+        let cell_y = y.floor() as i64;
+        let cell_z = z.floor() as i64;
+
+        // The two closest distances, and the value of the closest cell.
+        let mut f1 = INFINITY;
+        let mut f2 = INFINITY;
+        let mut cell_value = 0.0;
+
+        // Distance from the point to the closest face of its cell.
+        let border = (x - cell_x as f32).min(cell_x as f32 + 1.0 - x)
+            .min(y - cell_y as f32).min(cell_y as f32 + 1.0 - y)
+            .min(z - cell_z as f32).min(cell_z as f32 + 1.0 - z);
+
+        // A cell holds a single feature point, so the closest ones are not
+        // always in the neighbouring cells. Search the cells ring by ring
+        // around the cell of the point, until the next ring is too far to
+        // hold a point closer than the second closest one.
+        let mut ring = 0i64;
+        loop {
+            for i in range(cell_x - ring, cell_x + ring + 1) {
+                for j in range(cell_y - ring, cell_y + ring + 1) {
+                    for k in range(cell_z - ring, cell_z + ring + 1) {
+                        // The inner cells were searched with the previous rings.
+                        let cell_ring = max((i - cell_x).abs(), max((j - cell_y).abs(), (k - cell_z).abs()));
+                        if cell_ring != ring {
+                            continue;
+                        }
+
+                        // Skip the cells too far to hold a closer point.
+                        let gap = self.distance(cell_gap(x, i), cell_gap(y, j), cell_gap(z, k));
+                        if gap >= f2 {
+                            continue;
+                        }
+
+                        let mut rng = self.cell_rng(i, j, k);
+                        let feature_x = i as f32 + rng.next_f32();
+                        let feature_y = j as f32 + rng.next_f32();
+                        let feature_z = k as f32 + rng.next_f32();
+
+                        let d = self.distance(feature_x - x, feature_y - y, feature_z - z);
+                        if d < f1 {
+                            f2 = f1;
+                            f1 = d;
+                            cell_value = rng.next_f32() * 2.0 - 1.0;
+                        } else if d < f2 {
+                            f2 = d;
+                        }
+                    }
+                }
+            }
+
+            // The cells of the next ring are at least this far, along one axis.
+            if border + ring as f32 >= f2 {
+                break;
+            }
+            ring += 1;
+        }
+
+        match self.return_type {
+            F1 => f1,
+            F2 => f2,
+            F2MinusF1 => f2 - f1,
+            F1TimesF2 => f1 * f2,
+            CellValue => cell_value,
+        }
+    }
+}
+
+/// Distance along one axis from the coordinate `p` to the cell `cell`,
+/// zero inside the cell.
+fn cell_gap(p:f32, cell:i64) -> f32 {
+    (cell as f32 - p).max(p - (cell + 1) as f32).max(0.0)
+}
+
+impl BasisNoise for Worley {
+    /// Returns the noise value at the point(x,y,z), with one cell per unit
+    /// cube whatever the frequency.
+    fn get_basis(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.generate_noise(x, y, z)
+    }
+}
+
+/// Implements the noise generator common trait.
+impl Noise for Worley {
+    /// Returns the noise value at the point(x,y,z)
+    /// generated with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.generate_noise(x * self.frequency, y * self.frequency, z * self.frequency)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::{INFINITY, NEG_INFINITY, NAN};
+    use noise::{BasisNoise, Noise};
+    use super::{Worley, DistanceFunction, Euclidean, Chebyshev, Minkowski, F1, F2};
+
+    /// Closest distances found by searching every cell around the point.
+    fn brute_force(worley:&Worley, x:f32, y:f32, z:f32) -> (f32, f32) {
+        let (mut f1, mut f2) = (INFINITY, INFINITY);
+        let (cx, cy, cz) = (x.floor() as i64, y.floor() as i64, z.floor() as i64);
+        for i in range(cx - 4, cx + 5) {
+            for j in range(cy - 4, cy + 5) {
+                for k in range(cz - 4, cz + 5) {
+                    let mut rng = worley.cell_rng(i, j, k);
+                    let d = worley.distance(i as f32 + rng.next_f32() - x,
+                                            j as f32 + rng.next_f32() - y,
+                                            k as f32 + rng.next_f32() - z);
+                    if d < f1 {
+                        f2 = f1;
+                        f1 = d;
+                    } else if d < f2 {
+                        f2 = d;
+                    }
+                }
+            }
+        }
+        (f1, f2)
+    }
+
+    fn assert_finds_closest(distance:DistanceFunction) {
+        let mut worley = Worley::with_seed(3);
+        worley.set_distance_function(distance);
+        for n in range(0u, 500) {
+            let t = n as f32 * 0.173;
+            let (x, y, z) = (t * 1.31 - 20.0, t * -0.77 + 5.0, t * 0.29);
+            let (f1, f2) = brute_force(&worley, x, y, z);
+            worley.set_return_type(F1);
+            assert_eq!(worley.get_basis(x, y, z), f1);
+            worley.set_return_type(F2);
+            assert_eq!(worley.get_basis(x, y, z), f2);
+        }
+    }
+
+    #[test]
+    fn finds_the_closest_points() {
+        assert_finds_closest(Euclidean);
+        assert_finds_closest(Chebyshev);
+        assert_finds_closest(Minkowski(1.5));
+    }
+
+    #[test]
+    fn returns_nan_for_non_finite_points() {
+        let worley = Worley::new();
+        assert!(worley.get_basis(NAN, 0.0, 0.0).is_nan());
+        assert!(worley.get_basis(0.0, INFINITY, 0.0).is_nan());
+        assert!(worley.get_value(0.0, 0.0, NEG_INFINITY).is_nan());
+    }
+
+    #[test]
+    #[should_fail]
+    fn rejects_non_positive_minkowski_orders() {
+        let mut worley = Worley::new();
+        worley.set_distance_function(Minkowski(0.0));
+    }
+}
\ No newline at end of file
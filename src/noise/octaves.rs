@@ -0,0 +1,584 @@
+//! Octave summation shared by the fractal generators.
+//!
+//! Every octave samples the same basis, scaled by the lacuranity. Left as is,
+//! their lattices line up at the origin and along the axes, so each octave
+//! but the first is shifted by its own pseudo random offset, drawn from the
+//! seed of the generator, and can also be rotated relatively to the previous
+//! one.
+//!
+//! The 1D and 2D sums are not slices of the 3D sum: the offsets along the
+//! missing axes are dropped, and 2D has its own rotation.
+//!
+//! The bounded normalizations scale the sum from the range it can reach,
+//! derived from the range of a single octave measured on the basis in the
+//! same dimension, so that the sums of any basis spread over [-1, 1] in
+//! every dimension.
+
+/// Implements the setters of the octave parameters of a generator keeping
+/// them in its `octaves` field, e.g. `impl_octave_setters!(Perlin, persistence)`
+/// or `impl_octave_setters!(Fbm<N: BasisNoise>, persistence)`. Without
+/// `persistence`, the generator weights its octaves itself.
+macro_rules! impl_octave_setters(
+    ($name:ident $(<$param:ident: $bound:ident>)*) => (
+        impl $(<$param: $bound>)* $name $(<$param>)* {
+            /// Sets the number of octaves.
+            pub fn set_octave_count(&mut self, n:uint) {
+                self.octaves.octave_count = n;
+            }
+
+            /// Sets the frequency of the first octave.
+            pub fn set_frequency(&mut self, frequency:f32) {
+                self.octaves.frequency = frequency;
+            }
+
+            /// Set the frequency multiplier.
+            pub fn set_lacuranity(&mut self, lacuranity:f32) {
+                self.octaves.lacuranity = lacuranity;
+            }
+
+            /// Sets the seed the offsets of the octaves are drawn from.
+            pub fn set_octave_seed(&mut self, seed:u64) {
+                self.octaves.seed = seed;
+            }
+
+            /// Enables or disables the rotation of each octave
+            /// relatively to the previous one.
+            pub fn set_octave_rotation(&mut self, rotation:bool) {
+                self.octaves.rotation = rotation;
+            }
+
+            /// Sets how the sum of the octaves is scaled.
+            pub fn set_normalization(&mut self, normalization: ::noise::Normalization) {
+                self.octaves.normalization = normalization;
+            }
+        }
+    );
+    ($name:ident $(<$param:ident: $bound:ident>)*, persistence) => (
+        impl_octave_setters!($name $(<$param: $bound>)*)
+
+        impl $(<$param: $bound>)* $name $(<$param>)* {
+            /// Sets the persistence of the signal over succesive octaves.
+            pub fn set_persistence(&mut self, persistence:f32) {
+                self.octaves.persistence = persistence;
+            }
+        }
+    )
+)
+
+use math::NoiseFloat;
+use noise::{Normalization, Raw, AmplitudeSum, Bounded, UnitRange};
+use permutation::SplitMix64;
+
+/// Mixes the index of an octave into the seed of its offset.
+static OCTAVE_MIX: u64 = 0xD1B54A32D192ED03;
+
+/// The rotation applied between two successive octaves in 2D,
+/// by the angle whose cosine is 0.8 and sine is 0.6.
+static ROTATION_2D: [[f64, ..2], ..2] = [[0.8, -0.6],
+                                         [0.6,  0.8]];
+
+/// The rotation applied between two successive octaves in 3D.
+static ROTATION_3D: [[f64, ..3], ..3] = [[ 0.00,  0.80,  0.60],
+                                         [-0.80,  0.36, -0.48],
+                                         [-0.60, -0.48,  0.64]];
+
+/// Rotates the point(x,y) to the next octave.
+#[inline]
+fn rotate_2d<T: NoiseFloat>(x:T, y:T) -> (T, T) {
+    let m = &ROTATION_2D;
+    let c = |v:f64| -> T { NoiseFloat::from_f64(v) };
+    (c(m[0][0]) * x + c(m[0][1]) * y,
+     c(m[1][0]) * x + c(m[1][1]) * y)
+}
+
+/// Rotates the point(x,y,z) to the next octave.
+#[inline]
+fn rotate_3d<T: NoiseFloat>(x:T, y:T, z:T) -> (T, T, T) {
+    let m = &ROTATION_3D;
+    let c = |v:f64| -> T { NoiseFloat::from_f64(v) };
+    (c(m[0][0]) * x + c(m[0][1]) * y + c(m[0][2]) * z,
+     c(m[1][0]) * x + c(m[1][1]) * y + c(m[1][2]) * z,
+     c(m[2][0]) * x + c(m[2][1]) * y + c(m[2][2]) * z)
+}
+
+/// The octave parameters of a fractal Brownian motion.
+pub struct Octaves {
+    /// Controls the amount of details.
+    pub octave_count: uint,
+    /// The seed the offsets of the octaves are drawn from.
+    pub seed: u64,
+    /// The frequency of the first octave.
+    pub frequency: f32,
+    /// The frequency multiplier between successive octaves.
+    pub lacuranity: f32,
+    /// Controls the roughness.
+    pub persistence: f32,
+    /// Spectral exponent of the multifractals. When set, the amplitude of an
+    /// octave is its frequency relative to the first one to the power of
+    /// `-exponent`, instead of following the persistence.
+    pub exponent: Option<f32>,
+    /// Whether each octave is rotated relatively to the previous one.
+    pub rotation: bool,
+    /// How the sum of the octaves is scaled.
+    pub normalization: Normalization,
+    /// The lowest and highest values of a single octave of the basis in
+    /// 1, 2, 3 and 4 dimensions, see `BasisNoise::get_range`.
+    pub ranges: [(f32, f32), ..4],
+}
+
+/// The running state of a summation.
+struct Accumulator<T> {
+    /// The computed noise value.
+    value: T,
+    /// The amplitude of the current octave.
+    amplitude: T,
+    /// The total amplitude is used to normalize the final value.
+    total_amplitude: T,
+    /// The frequency of the current octave.
+    frequency: T,
+    /// The range of a single octave in the dimension of the sum.
+    range: (f32, f32),
+}
+
+impl<T: NoiseFloat> Accumulator<T> {
+    fn new(octaves: &Octaves, dimensions: uint) -> Accumulator<T> {
+        Accumulator {
+            value: NoiseFloat::from_f64(0.0),
+            amplitude: NoiseFloat::from_f64(1.0),
+            total_amplitude: NoiseFloat::from_f64(0.0),
+            frequency: NoiseFloat::from_f32(octaves.frequency),
+            range: octaves.ranges[dimensions - 1],
+        }
+    }
+
+    /// Adds the value of the current octave, then prepares the next one.
+    fn add(&mut self, octaves: &Octaves, v:T) {
+        self.value = self.value + v * self.amplitude;
+        self.advance(octaves);
+    }
+
+    /// Prepares the next octave.
+    fn advance(&mut self, octaves: &Octaves) {
+        self.frequency = self.frequency * NoiseFloat::from_f32(octaves.lacuranity);
+        self.total_amplitude = self.total_amplitude + self.amplitude;
+        self.amplitude = self.amplitude * NoiseFloat::from_f32(octaves.amplitude_ratio());
+    }
+
+    /// Returns the sum, normalized as requested.
+    fn finish(self, octaves: &Octaves) -> T {
+        let (lower, upper) = self.range;
+        let lower: T = NoiseFloat::from_f32(lower);
+        let upper: T = NoiseFloat::from_f32(upper);
+        normalize(octaves.normalization, self.value, self.total_amplitude,
+                  lower * self.total_amplitude, upper * self.total_amplitude)
+    }
+}
+
+/// Scales the sum `value` of octaves whose amplitudes sum to
+/// `total_amplitude`, and which can reach the range [lower, upper].
+fn normalize<T: NoiseFloat>(normalization: Normalization, value:T, total_amplitude:T,
+                            lower:T, upper:T) -> T {
+    let half: T = NoiseFloat::from_f64(0.5);
+    // Without any octave, there is nothing to scale.
+    if total_amplitude == NoiseFloat::from_f64(0.0) {
+        return value;
+    }
+    match normalization {
+        Raw => value,
+        AmplitudeSum => value / total_amplitude,
+        Bounded => bound(value, lower, upper),
+        UnitRange => bound(value, lower, upper) * half + half,
+    }
+}
+
+/// Maps `value` from [lower, upper] to [-1, 1], clamping the values
+/// found outside.
+fn bound<T: NoiseFloat>(value:T, lower:T, upper:T) -> T {
+    let zero: T = NoiseFloat::from_f64(0.0);
+    let one: T = NoiseFloat::from_f64(1.0);
+    let half: T = NoiseFloat::from_f64(0.5);
+    let half_range = (upper - lower) * half;
+    if half_range <= zero {
+        return zero;
+    }
+    ((value - (lower + upper) * half) / half_range).max(-one).min(one)
+}
+
+/// Returns the range of `|n|` for `n` in `range`.
+pub fn abs_range(range: (f32, f32)) -> (f32, f32) {
+    let (lower, upper) = range;
+    let highest = lower.abs().max(upper.abs());
+    if lower <= 0.0 && upper >= 0.0 {
+        (0.0, highest)
+    } else {
+        (lower.abs().min(upper.abs()), highest)
+    }
+}
+
+/// Returns the range of `a * b` for `a` in `range_a` and `b` in `range_b`.
+pub fn product_range(range_a: (f32, f32), range_b: (f32, f32)) -> (f32, f32) {
+    let (a0, a1) = range_a;
+    let (b0, b1) = range_b;
+    let (p0, p1, p2, p3) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    (p0.min(p1).min(p2).min(p3), p0.max(p1).max(p2).max(p3))
+}
+
+impl Octaves {
+    /// Creates the default octave parameters.
+    pub fn new() -> Octaves {
+        Octaves {
+            octave_count: 6,
+            seed: 0,
+            frequency: 1.0,
+            persistence: 0.5,
+            exponent: None,
+            lacuranity: 2.0,
+            rotation: false,
+            normalization: Bounded,
+            ranges: [(-1.0, 1.0), ..4],
+        }
+    }
+
+    /// Sets the range of a single octave in every dimension, for the
+    /// generators whose lower dimensions are slices of their 3D basis.
+    pub fn set_range(&mut self, range: (f32, f32)) {
+        self.ranges = [range, ..4];
+    }
+
+    /// Returns the offset of the octave `i` along x, y, z and w, in [0, 1).
+    /// The first octave is not shifted.
+    fn offset(&self, i:uint) -> [f32, ..4] {
+        if i == 0 {
+            return [0.0, ..4];
+        }
+        let mut rng = SplitMix64::new(self.seed ^ (i as u64 * OCTAVE_MIX));
+        [rng.next_f32(), rng.next_f32(), rng.next_f32(), rng.next_f32()]
+    }
+
+    /// Returns the ratio between the amplitudes of two successive octaves.
+    fn amplitude_ratio(&self) -> f32 {
+        match self.exponent {
+            Some(exponent) => self.lacuranity.powf(-exponent),
+            None => self.persistence,
+        }
+    }
+
+    /// Calls `octave` with the amplitude of each octave, in order.
+    pub fn each_amplitude(&self, octave: |f32|) {
+        let mut amplitude = 1.0;
+        for _ in range(0, self.octave_count) {
+            octave(amplitude);
+            amplitude *= self.amplitude_ratio();
+        }
+    }
+
+    /// Scales `value`, the sum returned by `fold_3d`, as requested by the
+    /// normalization. [lower, upper] is the range the sum can reach.
+    pub fn normalize_fold(&self, value:f32, lower:f32, upper:f32) -> f32 {
+        let mut total_amplitude = 0.0;
+        self.each_amplitude(|amplitude| total_amplitude += amplitude);
+        normalize(self.normalization, value, total_amplitude, lower, upper)
+    }
+
+    /// Sums the octaves of `octave` at the point(x).
+    /// The octaves are offset along x only.
+    pub fn sum_1d<T: NoiseFloat>(&self, x:T, octave: |T| -> T) -> T {
+        let mut acc = Accumulator::new(self, 1);
+        for i in range(0, self.octave_count) {
+            let o: T = NoiseFloat::from_f32(self.offset(i)[0]);
+            let v = octave(x * acc.frequency + o);
+            acc.add(self, v);
+        }
+        acc.finish(self)
+    }
+
+    /// Sums the octaves of `octave` at the point(x,y).
+    /// The octaves are offset along x and y, and rotated in the plane.
+    pub fn sum_2d<T: NoiseFloat>(&self, x:T, y:T, octave: |T, T| -> T) -> T {
+        let mut acc = Accumulator::new(self, 2);
+        let (mut x, mut y) = (x, y);
+        for i in range(0, self.octave_count) {
+            let o = self.offset(i);
+            let v = octave(x * acc.frequency + NoiseFloat::from_f32(o[0]),
+                           y * acc.frequency + NoiseFloat::from_f32(o[1]));
+            acc.add(self, v);
+            if self.rotation {
+                let (rx, ry) = rotate_2d(x, y);
+                x = rx;
+                y = ry;
+            }
+        }
+        acc.finish(self)
+    }
+
+    /// Sums the octaves of `octave` at the point(x,y,z).
+    pub fn sum_3d<T: NoiseFloat>(&self, x:T, y:T, z:T, octave: |T, T, T| -> T) -> T {
+        self.accumulate_3d(x, y, z, |sum, x, y, z, amplitude| sum + octave(x, y, z) * amplitude)
+            .finish(self)
+    }
+
+    /// Sums the octaves at the point(x,y,z), for the fractals weighting
+    /// each octave from the previous ones. `octave` is given the sum so
+    /// far, the coordinates and the amplitude of the octave, and returns
+    /// the new sum. The sum is returned as is, without normalization.
+    pub fn fold_3d<T: NoiseFloat>(&self, x:T, y:T, z:T, octave: |T, T, T, T, T| -> T) -> T {
+        self.accumulate_3d(x, y, z, octave).value
+    }
+
+    /// Runs the octaves at the point(x,y,z), `octave` computing the sum.
+    fn accumulate_3d<T: NoiseFloat>(&self, x:T, y:T, z:T,
+                                    octave: |T, T, T, T, T| -> T) -> Accumulator<T> {
+        let mut acc = Accumulator::new(self, 3);
+        let (mut x, mut y, mut z) = (x, y, z);
+        for i in range(0, self.octave_count) {
+            let o = self.offset(i);
+            acc.value = octave(acc.value,
+                               x * acc.frequency + NoiseFloat::from_f32(o[0]),
+                               y * acc.frequency + NoiseFloat::from_f32(o[1]),
+                               z * acc.frequency + NoiseFloat::from_f32(o[2]),
+                               acc.amplitude);
+            acc.advance(self);
+            if self.rotation {
+                let (rx, ry, rz) = rotate_3d(x, y, z);
+                x = rx;
+                y = ry;
+                z = rz;
+            }
+        }
+        acc
+    }
+
+    /// Sums the octaves of `octave` at the point(x,y,z,w).
+    /// Only the x, y and z coordinates are rotated.
+    pub fn sum_4d<T: NoiseFloat>(&self, x:T, y:T, z:T, w:T,
+                                 octave: |T, T, T, T| -> T) -> T {
+        let mut acc = Accumulator::new(self, 4);
+        let (mut x, mut y, mut z) = (x, y, z);
+        for i in range(0, self.octave_count) {
+            let o = self.offset(i);
+            let v = octave(x * acc.frequency + NoiseFloat::from_f32(o[0]),
+                           y * acc.frequency + NoiseFloat::from_f32(o[1]),
+                           z * acc.frequency + NoiseFloat::from_f32(o[2]),
+                           w * acc.frequency + NoiseFloat::from_f32(o[3]));
+            acc.add(self, v);
+            if self.rotation {
+                let (rx, ry, rz) = rotate_3d(x, y, z);
+                x = rx;
+                y = ry;
+                z = rz;
+            }
+        }
+        acc.finish(self)
+    }
+
+    /// Sums the octaves of `octave` at the point(x,y,z) and returns the
+    /// value with its derivatives. `octave` returns the derivatives
+    /// along the coordinates it is given.
+    pub fn sum_3d_with_derivative(&self, x:f32, y:f32, z:f32,
+                                  octave: |f32, f32, f32| -> (f32, [f32, ..3]))
+                                  -> (f32, [f32, ..3]) {
+        let mut acc = Accumulator::new(self, 3);
+        let mut derivative = [0.0f32, ..3];
+        // The rotation from the point to the current octave.
+        let mut m = [[1.0f32, 0.0, 0.0],
+                     [0.0, 1.0, 0.0],
+                     [0.0, 0.0, 1.0]];
+
+        for i in range(0, self.octave_count) {
+            let o = self.offset(i);
+            let f = acc.frequency;
+            let (v, d) = octave((m[0][0] * x + m[0][1] * y + m[0][2] * z) * f + o[0],
+                                (m[1][0] * x + m[1][1] * y + m[1][2] * z) * f + o[1],
+                                (m[2][0] * x + m[2][1] * y + m[2][2] * z) * f + o[2]);
+            // The octave is sampled at the frequency times the rotated
+            // point, so its derivatives go through the transposed rotation
+            // and are scaled by the frequency.
+            for j in range(0u, 3) {
+                let dj = m[0][j] * d[0] + m[1][j] * d[1] + m[2][j] * d[2];
+                derivative[j] += dj * acc.amplitude * f;
+            }
+            acc.add(self, v);
+            if self.rotation {
+                for j in range(0u, 3) {
+                    let (a, b, c) = rotate_3d(m[0][j], m[1][j], m[2][j]);
+                    m[0][j] = a;
+                    m[1][j] = b;
+                    m[2][j] = c;
+                }
+            }
+        }
+        // Normalize the derivatives along with the value,
+        // they vanish where the value is clamped.
+        let (lower, upper) = acc.range;
+        let half_range = (upper - lower) * 0.5 * acc.total_amplitude;
+        let value = acc.value - (lower + upper) * 0.5 * acc.total_amplitude;
+        let scale = match self.normalization {
+            _ if acc.total_amplitude == 0.0 => 1.0,
+            Raw => 1.0,
+            AmplitudeSum => 1.0 / acc.total_amplitude,
+            Bounded | UnitRange if half_range <= 0.0 => 0.0,
+            Bounded if value.abs() > half_range => 0.0,
+            Bounded => 1.0 / half_range,
+            UnitRange if value.abs() > half_range => 0.0,
+            UnitRange => 0.5 / half_range,
+        };
+        for j in range(0u, 3) {
+            derivative[j] *= scale;
+        }
+        (acc.finish(self), derivative)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::{INFINITY, NEG_INFINITY};
+    use fractals::billow::Billow;
+    use fractals::fbm::Fbm;
+    use fractals::hetero_terrain::HeteroTerrain;
+    use fractals::hybrid_multi::HybridMulti;
+    use fractals::ridged_multi::RidgedMulti;
+    use noise::{BasisNoise, Noise, Noise4D, Normalization, Raw, AmplitudeSum, Bounded, UnitRange};
+    use noises::perlin::Perlin;
+    use noises::simplex::Simplex;
+    use noises::worley::Worley;
+    use permutation::SplitMix64;
+
+    /// Returns the lowest and highest values of `noise` over many points.
+    fn sample_range<N: Noise>(noise:&N) -> (f32, f32) {
+        sample_points(|x, y, z, _| noise.get_value(x, y, z))
+    }
+
+    /// Returns the lowest and highest values of `f` over many points(x,y,z,w).
+    fn sample_points(f: |f32, f32, f32, f32| -> f32) -> (f32, f32) {
+        let mut rng = SplitMix64::new(1);
+        let (mut lower, mut upper) = (INFINITY, NEG_INFINITY);
+        for _ in range(0u, 20000) {
+            let x = rng.next_f32() * 200.0 - 100.0;
+            let y = rng.next_f32() * 200.0 - 100.0;
+            let z = rng.next_f32() * 200.0 - 100.0;
+            let w = rng.next_f32() * 200.0 - 100.0;
+            let v = f(x, y, z, w);
+            lower = lower.min(v);
+            upper = upper.max(v);
+        }
+        (lower, upper)
+    }
+
+    fn assert_within(range:(f32, f32), lower:f32, upper:f32) {
+        let (low, high) = range;
+        assert!(low >= lower && high <= upper, "{} is not within [{}, {}]", range, lower, upper);
+    }
+
+    /// Asserts that the generator built by `make` stays in the documented
+    /// ranges of the bounded normalizations.
+    fn assert_bounded<N: Noise>(make: |Normalization| -> N) {
+        assert_within(sample_range(&make(Bounded)), -1.0, 1.0);
+        assert_within(sample_range(&make(UnitRange)), 0.0, 1.0);
+    }
+
+    #[test]
+    fn bounded_normalizations_stay_in_range() {
+        assert_bounded(|n| { let mut perlin = Perlin::new(); perlin.set_normalization(n); perlin });
+        assert_bounded(|n| { let mut fbm = Fbm::new(Worley::new()); fbm.set_normalization(n); fbm });
+        assert_bounded(|n| { let mut billow = Billow::new(Perlin::new()); billow.set_normalization(n); billow });
+        assert_bounded(|n| { let mut ridged = RidgedMulti::new(Perlin::new()); ridged.set_normalization(n); ridged });
+        assert_bounded(|n| { let mut hybrid = HybridMulti::new(Perlin::new()); hybrid.set_normalization(n); hybrid });
+        assert_bounded(|n| { let mut terrain = HeteroTerrain::new(Perlin::new()); terrain.set_normalization(n); terrain });
+    }
+
+    #[test]
+    fn multifractal_sums_stay_within_their_bounds() {
+        // The bounds are derived, not sampled: the sums should never need
+        // to be clamped.
+        let (low, high) = sample_range(&RidgedMulti::new(Perlin::new()));
+        assert!(low > -1.0 && high < 1.0);
+        let (low, high) = sample_range(&HybridMulti::new(Perlin::new()));
+        assert!(low > -1.0 && high < 1.0);
+        let (low, high) = sample_range(&HeteroTerrain::new(Perlin::new()));
+        assert!(low > -1.0 && high < 1.0);
+    }
+
+    #[test]
+    fn bounded_normalization_uses_the_range_of_the_basis() {
+        // Worley distances are never negative, yet the bounded sum covers
+        // both halves of [-1, 1].
+        let (low, high) = sample_range(&Fbm::new(Worley::new()));
+        assert!(low < 0.0 && high > 0.0);
+    }
+
+    #[test]
+    fn amplitude_sum_stays_around_the_range_of_the_basis() {
+        let basis = Perlin::new();
+        let (lower, upper) = basis.get_range();
+        let mut fbm = Fbm::new(basis);
+        fbm.set_normalization(AmplitudeSum);
+        assert_within(sample_range(&fbm), lower - 0.1, upper + 0.1);
+    }
+
+    #[test]
+    fn single_octaves_spread_over_the_bounded_range_in_every_dimension() {
+        // The 1D Perlin basis only reaches half the range of the 3D one,
+        // each dimension is scaled from its own range.
+        let mut perlin = Perlin::new();
+        perlin.set_octave_count(1);
+        let mut simplex = Simplex::new();
+        simplex.set_octave_count(1);
+        let ranges = [sample_points(|x, _, _, _| perlin.get_1d(x)),
+                      sample_points(|x, y, _, _| perlin.get_2d(x, y)),
+                      sample_points(|x, y, z, _| perlin.get_value(x, y, z)),
+                      sample_points(|x, y, z, w| perlin.get_4d(x, y, z, w)),
+                      sample_points(|x, y, _, _| simplex.get_2d(x, y)),
+                      sample_points(|x, y, z, _| simplex.get_value(x, y, z)),
+                      sample_points(|x, y, z, w| simplex.get_4d(x, y, z, w))];
+        for &(low, high) in ranges.iter() {
+            assert!(low >= -1.0 && low < -0.9 && high > 0.9 && high <= 1.0,
+                    "({}, {}) does not spread over [-1, 1]", low, high);
+        }
+    }
+
+    /// Asserts that the details added by the octaves after the first one
+    /// are smaller where the first octave is low than where it is high.
+    /// `make` builds the generator with the given number of octaves.
+    fn assert_rougher_on_the_peaks<N: Noise>(make: |uint| -> N) {
+        let (base, full) = (make(1), make(6));
+        let mut rng = SplitMix64::new(5);
+        let mut points = Vec::new();
+        for _ in range(0u, 4000) {
+            let x = rng.next_f32() * 100.0 - 50.0;
+            let y = rng.next_f32() * 100.0 - 50.0;
+            let z = rng.next_f32() * 100.0 - 50.0;
+            let altitude = base.get_value(x, y, z);
+            points.push((altitude, (full.get_value(x, y, z) - altitude).abs()));
+        }
+        points.sort_by(|&(a, _), &(b, _)| a.partial_cmp(&b).unwrap());
+
+        // The mean details of the lowest and highest quarters.
+        let quarter = points.len() / 4;
+        let mean = |points: &[(f32, f32)]| {
+            let mut sum = 0.0f32;
+            for &(_, detail) in points.iter() {
+                sum += detail;
+            }
+            sum / points.len() as f32
+        };
+        let valleys = mean(points.slice_to(quarter));
+        let peaks = mean(points.slice_from(points.len() - quarter));
+        assert!(valleys * 1.5 < peaks, "valleys: {}, peaks: {}", valleys, peaks);
+    }
+
+    #[test]
+    fn multifractals_are_smooth_in_the_valleys_and_rough_on_the_peaks() {
+        assert_rougher_on_the_peaks(|n| {
+            let mut hybrid = HybridMulti::new(Perlin::new());
+            hybrid.set_octave_count(n);
+            hybrid.set_normalization(Raw);
+            hybrid
+        });
+        assert_rougher_on_the_peaks(|n| {
+            let mut terrain = HeteroTerrain::new(Perlin::new());
+            terrain.set_octave_count(n);
+            terrain.set_normalization(Raw);
+            terrain
+        });
+    }
+}
\ No newline at end of file
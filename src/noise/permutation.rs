@@ -0,0 +1,172 @@
+//! Permutation tables used to hash integer lattice coordinates.
+
+/// Implements the seed setters of a generator hashing the lattice through
+/// its `permutation` field, shuffled from its `seed` field. The seed also
+/// draws the offsets of the octaves kept in its `octaves` field, and the
+/// generator measures the range of its basis with `measure_ranges`.
+macro_rules! impl_seed_setters(
+    ($name:ident) => (
+        impl $name {
+            /// Creates a generator with default parameters
+            /// and a permutation shuffled from `seed`.
+            pub fn with_seed(seed:u64) -> $name {
+                let mut noise = $name::new();
+                noise.set_seed(seed);
+                noise
+            }
+
+            /// Sets the seed, then rebuilds the permutation, redraws the
+            /// offsets of the octaves from it and measures the range of
+            /// the new basis.
+            pub fn set_seed(&mut self, seed:u64) {
+                self.seed = seed;
+                self.permutation = ::permutation::PermutationTable::from_seed(seed);
+                self.octaves.seed = seed;
+                self.octaves.ranges = self.measure_ranges();
+            }
+
+            /// Returns the seed.
+            pub fn get_seed(&self) -> u64 {
+                self.seed
+            }
+        }
+    )
+)
+
+/// A pre-caclulated permutation of 256.
+static P: [uint, ..256] = [
+    151,  160,  137,  91,   90,   15,   131,  13,   201,  95,
+    96,   53,   194,  233,  7,    225,  140,  36,   103,  30,
+    69,   142,  8,    99,   37,   240,  21,   10,   23,   190,
+    6,    148,  247,  120,  234,  75,   0,    26,   197,  62, 
+    94,   252,  219,  203,  117,  35,   11,   32,   57,   177,
+    33,   88,   237,  149,  56,   87,   174,  20,   125,  136,
+    171,  168,  68,   175,  74,   165,  71,   134,  139,  48,
+    27,   166,  77,   146,  158,  231,  83,   111,  229,  122,
+    60,   211,  133,  230,  220,  105,  92,   41,   55,   46,
+    245,  40,   244,  102,  143,  54,   65,   25,   63,   161,
+    1,    216,  80,   73,   209,  76,   132,  187,  208,  89,
+    18,   169,  200,  196,  135,  130,  116,  188,  159,  86,
+    164,  100,  109,  198,  173,  186,  3,    64,   52,   217,
+    226,  250,  124,  123,  5,    202,  38,   147,  118,  126,
+    255,  82,   85,   212,  207,  206,  59,   227,  47,   16,
+    58,   17,   182,  189,  28,   42,   223,  183,  170,  213,
+    119,  248,  152,  2,    44,   154,  163,  70,   221,  153,
+    101,  155,  167,  43,   172,  9,    129,  22,   39,   253,
+    19,   98,   108,  110,  79,   113,  224,  232,  178,  185,
+    112,  104,  218,  246,  97,   228,  251,  34,   242,  193,
+    238,  210,  144,  12,   191,  179,  162,  241,  81,   51,
+    145,  235,  249,  14,   239,  107,  49,   192,  214,  31,
+    181,  199,  106,  157,  184,  84,   204,  176,  115,  121, 
+    50,   45,   127,  4,    150,  254,  138,  236,  205,  93,
+    222,  114,  67,   29,   24,   72,   243,  141,  128,  195,
+    78,   66,   215,  61,   156,  180,  
+];
+
+/// A doubled permutation of 256 used to hash lattice coordinates.
+///
+/// The permutation is stored twice in a row so that nested lookups such as
+/// `get(get(x) + y)` never need to wrap.
+pub struct PermutationTable {
+    values: [uint, ..512],
+}
+
+impl PermutationTable {
+    /// Creates the table from Ken Perlin's reference permutation.
+    pub fn reference() -> PermutationTable {
+        PermutationTable::from_permutation(&P)
+    }
+
+    /// Creates a table shuffled by a deterministic generator initialized
+    /// with `seed`. The same seed always gives the same table.
+    pub fn from_seed(seed: u64) -> PermutationTable {
+        let mut perm = [0u, ..256];
+        for i in range(0u, 256) {
+            perm[i] = i;
+        }
+
+        // Fisher-Yates shuffle.
+        let mut rng = SplitMix64::new(seed);
+        let mut i = 255u;
+        while i > 0 {
+            let j = (rng.next_u64() % (i as u64 + 1)) as uint;
+            let tmp = perm[i];
+            perm[i] = perm[j];
+            perm[j] = tmp;
+            i -= 1;
+        }
+        PermutationTable::from_permutation(&perm)
+    }
+
+    /// Doubles a permutation of 256 into a new table.
+    fn from_permutation(perm: &[uint, ..256]) -> PermutationTable {
+        let mut values = [0u, ..512];
+        for i in range(0u, 512) {
+            values[i] = perm[i & 0xFF];
+        }
+        PermutationTable { values: values }
+    }
+
+    /// Returns the permutation value at `index`, which is wrapped into the table.
+    #[inline]
+    pub fn get(&self, index: uint) -> uint {
+        self.values[index & 0x1FF]
+    }
+}
+
+/// SplitMix64 pseudo random number generator.
+///
+/// Only relies on wrapping 64 bits integer arithmetic, so its output is the
+/// same on every platform.
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator initialized with `seed`.
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    /// Returns the next pseudo random value.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state += 0x9E3779B97F4A7C15u64;
+        let mut z = self.state;
+        z = (z ^ (z >> 30)) * 0xBF58476D1CE4E5B9u64;
+        z = (z ^ (z >> 27)) * 0x94D049BB133111EBu64;
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next pseudo random value in [0, 1).
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PermutationTable;
+
+    #[test]
+    fn seeded_tables_do_not_drift() {
+        let a = PermutationTable::from_seed(42);
+        let b = PermutationTable::from_seed(0);
+        let expected_a = [203u, 217, 124, 199, 53, 101, 223, 240];
+        let expected_b = [99u, 179, 124, 78, 196, 203, 221, 113];
+        for i in range(0u, 8) {
+            assert_eq!(a.get(i), expected_a[i]);
+            assert_eq!(b.get(i), expected_b[i]);
+        }
+    }
+
+    #[test]
+    fn seeded_tables_are_permutations() {
+        let table = PermutationTable::from_seed(42);
+        let mut seen = [false, ..256];
+        for i in range(0u, 256) {
+            seen[table.get(i)] = true;
+            assert_eq!(table.get(i), table.get(i + 256));
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use noise::{Noise, VectorNoise};
+
+/// Offsets of the points a single noise is sampled at, one per component,
+/// so that the components are uncorrelated.
+static COMPONENT_OFFSETS: [[f32, ..3], ..3] = [[0.0, 0.0, 0.0],
+                                               [5.2, 1.3, 7.1],
+                                               [1.7, 9.2, 3.4]];
+
+/// Vector field whose components are read from three scalar noises,
+/// e.g. to warp the domain of a noise by noises of any kind.
+pub struct ComponentField<X, Y, Z> {
+    /// The noises of the x, y and z components.
+    x_component: X,
+    y_component: Y,
+    z_component: Z,
+}
+
+impl<X: Noise, Y: Noise, Z: Noise> ComponentField<X, Y, Z> {
+    /// Creates the vector field of the components `x_component`,
+    /// `y_component` and `z_component`.
+    pub fn new(x_component:X, y_component:Y, z_component:Z) -> ComponentField<X, Y, Z> {
+        ComponentField {
+            x_component: x_component,
+            y_component: y_component,
+            z_component: z_component,
+        }
+    }
+}
+
+/// Implements the vector noise generator common trait.
+impl<X: Noise, Y: Noise, Z: Noise> VectorNoise for ComponentField<X, Y, Z> {
+    /// Returns the vector at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        [self.x_component.get_value(x, y, z),
+         self.y_component.get_value(x, y, z),
+         self.z_component.get_value(x, y, z)]
+    }
+
+    /// Returns the vector at the point(x,y).
+    fn get_vector_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        [self.x_component.get_2d(x, y),
+         self.y_component.get_2d(x, y)]
+    }
+}
+
+/// Vector field whose components are all read from a single scalar noise,
+/// sampled at points offset from each other.
+pub struct OffsetField<N> {
+    /// The noise of every component.
+    source: N,
+}
+
+impl<N: Noise> OffsetField<N> {
+    /// Creates the vector field of `source`.
+    pub fn new(source:N) -> OffsetField<N> {
+        OffsetField { source: source }
+    }
+}
+
+/// Implements the vector noise generator common trait.
+impl<N: Noise> VectorNoise for OffsetField<N> {
+    /// Returns the vector at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        let mut v = [0.0f32, ..3];
+        for i in range(0u, 3) {
+            let o = &COMPONENT_OFFSETS[i];
+            v[i] = self.source.get_value(x + o[0], y + o[1], z + o[2]);
+        }
+        v
+    }
+
+    /// Returns the vector at the point(x,y).
+    fn get_vector_2d(&self, x:f32, y:f32) -> [f32, ..2] {
+        let o = &COMPONENT_OFFSETS;
+        [self.source.get_2d(x + o[0][0], y + o[0][1]),
+         self.source.get_2d(x + o[1][0], y + o[1][1])]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noise::Noise;
+    use noises::perlin::Perlin;
+    use noises::value::Value;
+    use transformers::domain_warp::DomainWarp;
+    use super::{COMPONENT_OFFSETS, ComponentField, OffsetField};
+
+    #[test]
+    fn warps_by_a_single_scalar_noise() {
+        let source = Perlin::with_seed(4);
+        let warp = Value::with_seed(8);
+        let mut warped = DomainWarp::new(Perlin::with_seed(4), OffsetField::new(Value::with_seed(8)));
+        warped.set_strength(0.5);
+        let o = &COMPONENT_OFFSETS;
+        for i in range(0u, 20) {
+            let (x, y, z) = (i as f32 * 0.41 - 3.0, i as f32 * 0.17, 1.5 - i as f32 * 0.23);
+            let dx = warp.get_value(x + o[0][0], y + o[0][1], z + o[0][2]);
+            let dy = warp.get_value(x + o[1][0], y + o[1][1], z + o[1][2]);
+            let dz = warp.get_value(x + o[2][0], y + o[2][1], z + o[2][2]);
+            assert_eq!(warped.get_value(x, y, z),
+                       source.get_value(x + 0.5 * dx, y + 0.5 * dy, z + 0.5 * dz));
+        }
+    }
+
+    #[test]
+    fn warps_by_three_scalar_noises() {
+        let source = Perlin::with_seed(4);
+        let components = [Perlin::with_seed(1), Perlin::with_seed(2), Perlin::with_seed(3)];
+        let field = ComponentField::new(Perlin::with_seed(1), Perlin::with_seed(2), Perlin::with_seed(3));
+        let mut warped = DomainWarp::new(Perlin::with_seed(4), field);
+        warped.set_strength(0.25);
+        for i in range(0u, 20) {
+            let (x, y) = (i as f32 * 0.41 - 3.0, i as f32 * 0.17);
+            let dx = components[0].get_2d(x, y);
+            let dy = components[1].get_2d(x, y);
+            assert_eq!(warped.get_2d(x, y), source.get_2d(x + 0.25 * dx, y + 0.25 * dy));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use noise::{Noise, Noise4D};
+
+/// Displaces the point before sampling its source, each coordinate
+/// being moved by the value of its own noise, as libnoise does. The 1D
+/// and 2D points only move along their own axes, and the w coordinate of
+/// the 4D points is left unchanged.
+///
+/// To displace by a vector field, use `DomainWarp`.
+pub struct Displace<S, X, Y, Z> {
+    /// The transformed noise.
+    source: S,
+    /// Displace the x, y and z coordinates.
+    x_displace: X,
+    y_displace: Y,
+    z_displace: Z,
+}
+
+impl<S: Noise, X: Noise, Y: Noise, Z: Noise> Displace<S, X, Y, Z> {
+    /// Creates a displacement of `source`, the coordinates being moved
+    /// by `x_displace`, `y_displace` and `z_displace`.
+    pub fn new(source:S, x_displace:X, y_displace:Y, z_displace:Z) -> Displace<S, X, Y, Z> {
+        Displace {
+            source: source,
+            x_displace: x_displace,
+            y_displace: y_displace,
+            z_displace: z_displace,
+        }
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise, X: Noise, Y: Noise, Z: Noise> Noise for Displace<S, X, Y, Z> {
+    /// Returns the noise value of the source at the point(x,y,z) displaced.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.source.get_value(x + self.x_displace.get_value(x, y, z),
+                              y + self.y_displace.get_value(x, y, z),
+                              z + self.z_displace.get_value(x, y, z))
+    }
+
+    /// Returns the noise value of the source at the point(x) displaced.
+    fn get_1d(&self, x:f32) -> f32 {
+        self.source.get_1d(x + self.x_displace.get_1d(x))
+    }
+
+    /// Returns the noise value of the source at the point(x,y) displaced.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.source.get_2d(x + self.x_displace.get_2d(x, y),
+                           y + self.y_displace.get_2d(x, y))
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D, X: Noise4D, Y: Noise4D, Z: Noise4D> Noise4D for Displace<S, X, Y, Z> {
+    /// Returns the noise value of the source at the point(x,y,z,w) displaced.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.source.get_4d(x + self.x_displace.get_4d(x, y, z, w),
+                           y + self.y_displace.get_4d(x, y, z, w),
+                           z + self.z_displace.get_4d(x, y, z, w),
+                           w)
+    }
+}
@@ -0,0 +1,111 @@
+use noise::{Noise, Noise4D, VectorNoise};
+
+/// Domain warping generator.
+///
+/// Displaces the point by a vector field before sampling the source:
+/// `q = p + k·f(p)`. With more than one iteration the displacement is fed
+/// back into the warp, as in Quilez's warping: `q = p + k·f(p + k·f(p))`,
+/// giving swirling patterns.
+///
+/// The 2D points are warped by the 2D field and the 1D points by its x
+/// component along the x axis. The 4D points are warped in 3D, their w
+/// coordinate is left unchanged. To warp by scalar noises, see
+/// `ComponentField` and `OffsetField`.
+pub struct DomainWarp<S, W> {
+    /// The noise sampled at the warped point.
+    source: S,
+    /// The vector field giving the displacement.
+    warp: W,
+    /// Scales the displacement.
+    strength: f32,
+    /// How many times the warp is applied.
+    iterations: uint,
+}
+
+impl<S: Noise, W: VectorNoise> DomainWarp<S, W> {
+    /// Creates a domain warp of `source` by `warp`,
+    /// applied once with a unit strength.
+    pub fn new(source:S, warp:W) -> DomainWarp<S, W> {
+        DomainWarp {
+            source: source,
+            warp: warp,
+            strength: 1.0,
+            iterations: 1,
+        }
+    }
+
+    /// Sets the scale of the displacement.
+    pub fn set_strength(&mut self, strength:f32) {
+        self.strength = strength;
+    }
+
+    /// Sets how many times the warp is applied, zero samples
+    /// the source as is.
+    pub fn set_iterations(&mut self, iterations:uint) {
+        self.iterations = iterations;
+    }
+
+    /// Returns the point(x,y,z) warped with the current parameters.
+    pub fn warp_point(&self, x:f32, y:f32, z:f32) -> (f32, f32, f32) {
+        // The displacement computed by the previous iteration.
+        let mut d = [0.0f32, ..3];
+
+        for _ in range(0, self.iterations) {
+            d = self.warp.get_vector_3d(x + self.strength * d[0],
+                                        y + self.strength * d[1],
+                                        z + self.strength * d[2]);
+        }
+        (x + self.strength * d[0],
+         y + self.strength * d[1],
+         z + self.strength * d[2])
+    }
+
+    /// Returns the point(x,y) warped with the current parameters.
+    pub fn warp_point_2d(&self, x:f32, y:f32) -> (f32, f32) {
+        let mut d = [0.0f32, ..2];
+
+        for _ in range(0, self.iterations) {
+            d = self.warp.get_vector_2d(x + self.strength * d[0],
+                                        y + self.strength * d[1]);
+        }
+        (x + self.strength * d[0],
+         y + self.strength * d[1])
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise, W: VectorNoise> Noise for DomainWarp<S, W> {
+    /// Returns the noise value of the source at the
+    /// point(x,y,z) warped with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let (x, y, z) = self.warp_point(x, y, z);
+        self.source.get_value(x, y, z)
+    }
+
+    /// Returns the noise value of the source at the
+    /// point(x) warped with the current parameters.
+    fn get_1d(&self, x:f32) -> f32 {
+        let mut d = 0.0f32;
+        for _ in range(0, self.iterations) {
+            d = self.warp.get_vector_2d(x + self.strength * d, 0.0)[0];
+        }
+        self.source.get_1d(x + self.strength * d)
+    }
+
+    /// Returns the noise value of the source at the
+    /// point(x,y) warped with the current parameters.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        let (x, y) = self.warp_point_2d(x, y);
+        self.source.get_2d(x, y)
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D, W: VectorNoise> Noise4D for DomainWarp<S, W> {
+    /// Returns the noise value of the source at the
+    /// point(x,y,z,w) warped with the current parameters.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let (x, y, z) = self.warp_point(x, y, z);
+        self.source.get_4d(x, y, z, w)
+    }
+}
@@ -0,0 +1,26 @@
+use noise::{NoiseWithDerivative, VectorNoise};
+
+/// Gradient field of a noise.
+///
+/// Returns the gradient of the source, computed from its analytic
+/// derivatives. It points uphill, with a length equal to the slope.
+pub struct GradientField<N> {
+    /// The noise to differentiate.
+    source: N,
+}
+
+impl<N: NoiseWithDerivative> GradientField<N> {
+    /// Creates the gradient field of `source`.
+    pub fn new(source:N) -> GradientField<N> {
+        GradientField { source: source }
+    }
+}
+
+/// Implements the vector noise generator common trait.
+impl<N: NoiseWithDerivative> VectorNoise for GradientField<N> {
+    /// Returns the gradient at the point(x,y,z).
+    fn get_vector_3d(&self, x:f32, y:f32, z:f32) -> [f32, ..3] {
+        let (_, d) = self.source.get_value_with_derivative(x, y, z);
+        d
+    }
+}
@@ -0,0 +1,8 @@
+pub mod component_field;
+pub mod displace;
+pub mod domain_warp;
+pub mod gradient_field;
+pub mod rotate_point;
+pub mod scale_point;
+pub mod translate_point;
+pub mod turbulence;
\ No newline at end of file
@@ -0,0 +1,164 @@
+use noise::{Noise, Noise4D};
+
+/// Rotates the point before sampling its source, e.g. to hide the
+/// features aligned with the lattice of the noise.
+///
+/// The rotation is given either by Euler angles or by a quaternion.
+/// The 1D and 2D points are forwarded to the same dimension of the source
+/// while the rotation keeps them on its axis or in its plane, and to its 3D
+/// values otherwise. The w coordinate of the 4D points is left unchanged.
+pub struct RotatePoint<S> {
+    /// The transformed noise.
+    source: S,
+    /// The rotation matrix, by rows.
+    matrix: [[f32, ..3], ..3],
+}
+
+impl<S: Noise> RotatePoint<S> {
+    /// Creates a rotation of `source` leaving the points unchanged.
+    pub fn new(source:S) -> RotatePoint<S> {
+        RotatePoint {
+            source: source,
+            matrix: [[1.0, 0.0, 0.0],
+                     [0.0, 1.0, 0.0],
+                     [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// Sets the rotation from Euler angles around the x, y and z axes,
+    /// in radians, as libnoise does.
+    pub fn set_angles(&mut self, x:f32, y:f32, z:f32) {
+        let (x_sin, x_cos) = (x.sin(), x.cos());
+        let (y_sin, y_cos) = (y.sin(), y.cos());
+        let (z_sin, z_cos) = (z.sin(), z.cos());
+
+        self.matrix = [[y_sin * x_sin * z_sin + y_cos * z_cos,
+                        x_cos * z_sin,
+                        y_sin * z_cos - y_cos * x_sin * z_sin],
+                       [y_sin * x_sin * z_cos - y_cos * z_sin,
+                        x_cos * z_cos,
+                        -y_cos * x_sin * z_cos - y_sin * z_sin],
+                       [-y_sin * x_cos,
+                        x_sin,
+                        y_cos * x_cos]];
+    }
+
+    /// Sets the rotation from the quaternion w + xi + yj + zk,
+    /// which is normalized first.
+    pub fn set_quaternion(&mut self, w:f32, x:f32, y:f32, z:f32) {
+        let norm = (w * w + x * x + y * y + z * z).sqrt();
+        if norm == 0.0 {
+            return;
+        }
+        let (w, x, y, z) = (w / norm, x / norm, y / norm, z / norm);
+
+        self.matrix = [[1.0 - 2.0 * (y * y + z * z),
+                        2.0 * (x * y - w * z),
+                        2.0 * (x * z + w * y)],
+                       [2.0 * (x * y + w * z),
+                        1.0 - 2.0 * (x * x + z * z),
+                        2.0 * (y * z - w * x)],
+                       [2.0 * (x * z - w * y),
+                        2.0 * (y * z + w * x),
+                        1.0 - 2.0 * (x * x + y * y)]];
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise> Noise for RotatePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z) rotated.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let m = &self.matrix;
+        self.source.get_value(m[0][0] * x + m[0][1] * y + m[0][2] * z,
+                              m[1][0] * x + m[1][1] * y + m[1][2] * z,
+                              m[2][0] * x + m[2][1] * y + m[2][2] * z)
+    }
+
+    /// Returns the noise value of the source at the point(x) rotated.
+    fn get_1d(&self, x:f32) -> f32 {
+        let m = &self.matrix;
+        if m[1][0] == 0.0 && m[2][0] == 0.0 {
+            self.source.get_1d(m[0][0] * x)
+        } else if m[2][0] == 0.0 {
+            self.source.get_2d(m[0][0] * x, m[1][0] * x)
+        } else {
+            self.source.get_value(m[0][0] * x, m[1][0] * x, m[2][0] * x)
+        }
+    }
+
+    /// Returns the noise value of the source at the point(x,y) rotated.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        let m = &self.matrix;
+        if m[2][0] == 0.0 && m[2][1] == 0.0 {
+            self.source.get_2d(m[0][0] * x + m[0][1] * y,
+                               m[1][0] * x + m[1][1] * y)
+        } else {
+            self.source.get_value(m[0][0] * x + m[0][1] * y,
+                                  m[1][0] * x + m[1][1] * y,
+                                  m[2][0] * x + m[2][1] * y)
+        }
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D> Noise4D for RotatePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z,w) rotated.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let m = &self.matrix;
+        self.source.get_4d(m[0][0] * x + m[0][1] * y + m[0][2] * z,
+                           m[1][0] * x + m[1][1] * y + m[1][2] * z,
+                           m[2][0] * x + m[2][1] * y + m[2][2] * z,
+                           w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+    use noise::Noise;
+    use noises::perlin::Perlin;
+    use super::RotatePoint;
+
+    /// Checks that the Euler angles and the quaternion give the same matrix.
+    fn assert_same_rotation(euler:[f32, ..3], quaternion:[f32, ..4]) {
+        let mut a = RotatePoint::new(Perlin::new());
+        a.set_angles(euler[0], euler[1], euler[2]);
+        let mut b = RotatePoint::new(Perlin::new());
+        b.set_quaternion(quaternion[0], quaternion[1], quaternion[2], quaternion[3]);
+        for i in range(0u, 3) {
+            for j in range(0u, 3) {
+                assert!((a.matrix[i][j] - b.matrix[i][j]).abs() < 1e-6,
+                        "{} {}: {} {}", i, j, a.matrix[i][j], b.matrix[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_angles_match_quaternions() {
+        for &angle in [0.3f32, -1.2, PI / 2.0, 2.5].iter() {
+            let (s, c) = ((angle / 2.0).sin(), (angle / 2.0).cos());
+            assert_same_rotation([angle, 0.0, 0.0], [c, s, 0.0, 0.0]);
+            assert_same_rotation([0.0, angle, 0.0], [c, 0.0, s, 0.0]);
+            // As in libnoise, the angle around z turns the other way.
+            assert_same_rotation([0.0, 0.0, angle], [c, 0.0, 0.0, -s]);
+        }
+    }
+
+    #[test]
+    fn quaternions_are_normalized() {
+        let (s, c) = ((0.4f32).sin(), (0.4f32).cos());
+        assert_same_rotation([0.8, 0.0, 0.0], [3.0 * c, 3.0 * s, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn rotations_in_the_plane_stay_2d() {
+        let source = Perlin::with_seed(9);
+        let mut rotate = RotatePoint::new(Perlin::with_seed(9));
+        rotate.set_angles(0.0, 0.0, 0.7);
+        let (s, c) = ((0.7f32).sin(), (0.7f32).cos());
+        for i in range(0u, 20) {
+            let (x, y) = (i as f32 * 0.53 - 5.0, i as f32 * 0.29 + 1.0);
+            assert_eq!(rotate.get_2d(x, y), source.get_2d(c * x + s * y, -s * x + c * y));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use noise::{Noise, Noise4D};
+
+/// Scales the point before sampling its source, independently along
+/// each axis: stretches or squeezes the noise. The w coordinate of the
+/// 4D points is left unchanged.
+pub struct ScalePoint<S> {
+    /// The transformed noise.
+    source: S,
+    /// The multiplier of the coordinates along each axis.
+    scale: [f32, ..3],
+}
+
+impl<S: Noise> ScalePoint<S> {
+    /// Creates a scaling of `source` leaving the points unchanged.
+    pub fn new(source:S) -> ScalePoint<S> {
+        ScalePoint {
+            source: source,
+            scale: [1.0, ..3],
+        }
+    }
+
+    /// Sets the multiplier of the coordinates along the x, y and z axes.
+    pub fn set_scale(&mut self, x:f32, y:f32, z:f32) {
+        self.scale = [x, y, z];
+    }
+
+    /// Sets the multiplier of the coordinates along the x axis.
+    pub fn set_x_scale(&mut self, x:f32) {
+        self.scale[0] = x;
+    }
+
+    /// Sets the multiplier of the coordinates along the y axis.
+    pub fn set_y_scale(&mut self, y:f32) {
+        self.scale[1] = y;
+    }
+
+    /// Sets the multiplier of the coordinates along the z axis.
+    pub fn set_z_scale(&mut self, z:f32) {
+        self.scale[2] = z;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise> Noise for ScalePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z) scaled.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.source.get_value(x * self.scale[0],
+                              y * self.scale[1],
+                              z * self.scale[2])
+    }
+
+    /// Returns the noise value of the source at the point(x) scaled.
+    fn get_1d(&self, x:f32) -> f32 {
+        self.source.get_1d(x * self.scale[0])
+    }
+
+    /// Returns the noise value of the source at the point(x,y) scaled.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.source.get_2d(x * self.scale[0], y * self.scale[1])
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D> Noise4D for ScalePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z,w) scaled.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.source.get_4d(x * self.scale[0],
+                           y * self.scale[1],
+                           z * self.scale[2],
+                           w)
+    }
+}
@@ -0,0 +1,71 @@
+use noise::{Noise, Noise4D};
+
+/// Moves the point before sampling its source. The w coordinate of the
+/// 4D points is left unchanged.
+pub struct TranslatePoint<S> {
+    /// The transformed noise.
+    source: S,
+    /// The distance the point is moved along each axis.
+    translation: [f32, ..3],
+}
+
+impl<S: Noise> TranslatePoint<S> {
+    /// Creates a translation of `source` leaving the points unchanged.
+    pub fn new(source:S) -> TranslatePoint<S> {
+        TranslatePoint {
+            source: source,
+            translation: [0.0, ..3],
+        }
+    }
+
+    /// Sets the distance the point is moved along the x, y and z axes.
+    pub fn set_translation(&mut self, x:f32, y:f32, z:f32) {
+        self.translation = [x, y, z];
+    }
+
+    /// Sets the distance the point is moved along the x axis.
+    pub fn set_x_translation(&mut self, x:f32) {
+        self.translation[0] = x;
+    }
+
+    /// Sets the distance the point is moved along the y axis.
+    pub fn set_y_translation(&mut self, y:f32) {
+        self.translation[1] = y;
+    }
+
+    /// Sets the distance the point is moved along the z axis.
+    pub fn set_z_translation(&mut self, z:f32) {
+        self.translation[2] = z;
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise> Noise for TranslatePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z) moved.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        self.source.get_value(x + self.translation[0],
+                              y + self.translation[1],
+                              z + self.translation[2])
+    }
+
+    /// Returns the noise value of the source at the point(x) moved.
+    fn get_1d(&self, x:f32) -> f32 {
+        self.source.get_1d(x + self.translation[0])
+    }
+
+    /// Returns the noise value of the source at the point(x,y) moved.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        self.source.get_2d(x + self.translation[0], y + self.translation[1])
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D> Noise4D for TranslatePoint<S> {
+    /// Returns the noise value of the source at the point(x,y,z,w) moved.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        self.source.get_4d(x + self.translation[0],
+                           y + self.translation[1],
+                           z + self.translation[2],
+                           w)
+    }
+}
@@ -0,0 +1,125 @@
+use noise::{Noise, Noise4D};
+use noises::perlin::Perlin;
+
+/// Offsets of the points the distortion noises are sampled at, one per
+/// axis, so that the three displacements differ even for a same seed.
+/// These are the ones of libnoise.
+static DISTORT_OFFSETS: [[f32, ..3], ..3] = [[12414.0 / 65536.0, 65124.0 / 65536.0, 31337.0 / 65536.0],
+                                             [26519.0 / 65536.0, 18128.0 / 65536.0, 60493.0 / 65536.0],
+                                             [53820.0 / 65536.0, 11213.0 / 65536.0, 44845.0 / 65536.0]];
+
+/// Turbulence transformer, after libnoise.
+///
+/// Randomly displaces the point before sampling the source, each
+/// coordinate being moved by its own Perlin noise. Turns smooth noises
+/// into fire, smoke or marble veins. The 1D and 2D points only move along
+/// their own axes, and the w coordinate of the 4D points is left unchanged.
+pub struct Turbulence<S> {
+    /// The noise sampled at the displaced point.
+    source: S,
+    /// Displace the x, y and z coordinates.
+    x_distort: Perlin,
+    y_distort: Perlin,
+    z_distort: Perlin,
+    /// Scales the displacement.
+    power: f32,
+    /// The seed of the x distortion, the y and z ones follow it.
+    seed: u64,
+}
+
+impl<S: Noise> Turbulence<S> {
+    /// Creates a turbulence transformer of `source` with default parameters.
+    pub fn new(source:S) -> Turbulence<S> {
+        let mut turbulence = Turbulence {
+            source: source,
+            x_distort: Perlin::new(),
+            y_distort: Perlin::new(),
+            z_distort: Perlin::new(),
+            power: 1.0,
+            seed: 0,
+        };
+        turbulence.set_seed(0);
+        turbulence.set_roughness(3);
+        turbulence
+    }
+
+    /// Sets the seed of the distortion noises.
+    pub fn set_seed(&mut self, seed:u64) {
+        self.seed = seed;
+        self.x_distort.set_seed(seed);
+        self.y_distort.set_seed(seed + 1);
+        self.z_distort.set_seed(seed + 2);
+    }
+
+    /// Returns the seed.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the frequency of the displacement.
+    pub fn set_frequency(&mut self, frequency:f32) {
+        self.x_distort.set_frequency(frequency);
+        self.y_distort.set_frequency(frequency);
+        self.z_distort.set_frequency(frequency);
+    }
+
+    /// Sets the scale of the displacement.
+    pub fn set_power(&mut self, power:f32) {
+        self.power = power;
+    }
+
+    /// Sets the number of octaves of the distortion noises,
+    /// the higher the rougher the displacement.
+    pub fn set_roughness(&mut self, roughness:uint) {
+        self.x_distort.set_octave_count(roughness);
+        self.y_distort.set_octave_count(roughness);
+        self.z_distort.set_octave_count(roughness);
+    }
+}
+
+/// Implements the noise generator common trait.
+impl<S: Noise> Noise for Turbulence<S> {
+    /// Returns the noise value of the source at the
+    /// point(x,y,z) displaced with the current parameters.
+    fn get_value(&self, x:f32, y:f32, z:f32) -> f32 {
+        let o = &DISTORT_OFFSETS;
+        let dx = self.x_distort.get_value(x + o[0][0], y + o[0][1], z + o[0][2]);
+        let dy = self.y_distort.get_value(x + o[1][0], y + o[1][1], z + o[1][2]);
+        let dz = self.z_distort.get_value(x + o[2][0], y + o[2][1], z + o[2][2]);
+        self.source.get_value(x + dx * self.power,
+                              y + dy * self.power,
+                              z + dz * self.power)
+    }
+
+    /// Returns the noise value of the source at the
+    /// point(x) displaced with the current parameters.
+    fn get_1d(&self, x:f32) -> f32 {
+        let dx = self.x_distort.get_1d(x + DISTORT_OFFSETS[0][0]);
+        self.source.get_1d(x + dx * self.power)
+    }
+
+    /// Returns the noise value of the source at the
+    /// point(x,y) displaced with the current parameters.
+    fn get_2d(&self, x:f32, y:f32) -> f32 {
+        let o = &DISTORT_OFFSETS;
+        let dx = self.x_distort.get_2d(x + o[0][0], y + o[0][1]);
+        let dy = self.y_distort.get_2d(x + o[1][0], y + o[1][1]);
+        self.source.get_2d(x + dx * self.power, y + dy * self.power)
+    }
+}
+
+/// Implements the 4D noise generator trait.
+impl<S: Noise4D> Noise4D for Turbulence<S> {
+    /// Returns the noise value of the source at the
+    /// point(x,y,z,w) displaced with the current parameters.
+    fn get_4d(&self, x:f32, y:f32, z:f32, w:f32) -> f32 {
+        let o = &DISTORT_OFFSETS;
+        let dx = self.x_distort.get_4d(x + o[0][0], y + o[0][1], z + o[0][2], w);
+        let dy = self.y_distort.get_4d(x + o[1][0], y + o[1][1], z + o[1][2], w);
+        let dz = self.z_distort.get_4d(x + o[2][0], y + o[2][1], z + o[2][2], w);
+        self.source.get_4d(x + dx * self.power,
+                           y + dy * self.power,
+                           z + dz * self.power,
+                           w)
+    }
+}